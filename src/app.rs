@@ -1,16 +1,25 @@
-use egui::{CentralPanel, Frame, Margin, SidePanel, Vec2};
+use std::{path::PathBuf, time::Duration};
+
+use egui::{CentralPanel, Frame, Key, KeyboardShortcut, Margin, Modifiers, SidePanel, Vec2};
 
 use crate::{
     logic::truth_table::TruthTable,
     ui::{
         components::{
-            map_view,
-            menubar::{self, WindowState},
-            properties_view, table_view, variable_view,
-        }, 
-        events::{self, EventQueue}, 
-        modals, 
-        variable::*
+            expression_view::{self, ExpressionViewState},
+            history_view,
+            map_view::{self, MapViewState},
+            menubar::{self, Preferences, WindowState},
+            properties_view, table_view,
+            tableau_view::{self, TableauViewState},
+            variable_view,
+        },
+        events::{self, Event, EventQueue},
+        history::History,
+        modals,
+        selection::{HoveredTerm, Selection},
+        solutions::SolutionCache,
+        variable::*,
     },
 };
 
@@ -18,30 +27,79 @@ use crate::{
 pub struct AppState {
     pub modals: modals::ModalState,
     pub window_state: WindowState,
+    pub preferences: Preferences,
     pub variables: VariableStore,
 
     pub table: TruthTable,
-    pub events: EventQueue
+    pub map: MapViewState,
+    pub expression: ExpressionViewState,
+    pub tableau: TableauViewState,
+    pub events: EventQueue,
+    pub history: History,
+    pub selection: Selection,
+    /// Set by the map view while the pointer is over a loop
+    pub hovered_term: Option<HoveredTerm>,
+    pub solutions: SolutionCache,
+
+    /// Where the project was last opened from or saved to
+    pub project_path: Option<PathBuf>,
 }
 
-impl AppState {}
+impl AppState {
+    /// Queues undo and redo for their shortcuts unless a text field has focus
+    fn shortcuts(&mut self, ctx: &egui::Context) {
+        if ctx.wants_keyboard_input() {
+            return;
+        }
+
+        let redo = KeyboardShortcut::new(Modifiers::COMMAND | Modifiers::SHIFT, Key::Z);
+        let undo = KeyboardShortcut::new(Modifiers::COMMAND, Key::Z);
+
+        // Undo ignores Shift when matching, so redo has to be checked first
+        ctx.input_mut(|i| {
+            if i.consume_shortcut(&redo) {
+                self.events.push(Event::Redo);
+            } else if i.consume_shortcut(&undo) {
+                self.events.push(Event::Undo);
+            }
+        });
+    }
+}
 
 pub fn app() -> eframe::Result {
     let native_options = eframe::NativeOptions::default();
+    // The name also keys the storage preferences are saved in
     eframe::run_native(
-        "egui",
+        "karny",
         native_options,
-        Box::new(|_cc| Ok(Box::new(AppState::default()))),
+        Box::new(|cc| {
+            Ok(Box::new(AppState {
+                preferences: Preferences::load(cc.storage),
+                ..Default::default()
+            }))
+        }),
     )
 }
 
 impl eframe::App for AppState {
+    fn save(&mut self, storage: &mut dyn eframe::Storage) {
+        self.preferences.save(storage);
+    }
+
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        self.shortcuts(ctx);
         let events = self.events.take_all();
         events::dispatch_all(self, events);
-        
-        menubar::update(ctx, &mut self.window_state);
+
+        menubar::update(
+            ctx,
+            &mut self.window_state,
+            &mut self.preferences,
+            &mut self.events,
+        );
         modals::update(ctx, self);
+        history_view::update(ctx, self);
+        tableau_view::update(ctx, self);
 
         SidePanel::left("left_panel")
             .resizable(true)
@@ -52,6 +110,7 @@ impl eframe::App for AppState {
                 ui.separator();
                 properties_view::render(ui, self);
             });
+        expression_view::update(ctx, self);
 
         // TODO: Better split pane
         // Maybe implement egui-dock in the future
@@ -66,22 +125,36 @@ impl eframe::App for AppState {
                     .fill(ui.visuals().faint_bg_color);
 
                 // Left
+                if !self.window_state.map_view {
+                    self.hovered_term = None;
+                }
                 if self.window_state.map_view {
                     SidePanel::left("map_view")
                         .resizable(true)
                         .frame(frame)
                         .default_width(ui.available_width() / 2.0)
                         .show_inside(ui, |ui| {
-                            map_view::render(ui);
+                            map_view::render(ui, self);
                         });
                 }
 
                 // Right
                 if self.window_state.table_view {
                     CentralPanel::default().frame(frame).show_inside(ui, |ui| {
-                        table_view::render(ui, &mut self.table);
+                        table_view::render(
+                            ui,
+                            &self.table,
+                            &mut self.selection,
+                            self.hovered_term,
+                            &mut self.events,
+                        );
                     });
                 }
             });
+
+        // Nothing else repaints once a worker finishes
+        if self.solutions.is_busy() {
+            ctx.request_repaint_after(Duration::from_millis(50));
+        }
     }
 }
@@ -0,0 +1,345 @@
+use std::{
+    io::{self, Read},
+    process::ExitCode,
+};
+
+use clap::{Parser, ValueEnum};
+
+use crate::logic::{
+    canonical::{Canonical, TermKind},
+    expression::{Expression, ParseError},
+    kmap::{KMap, KMapFormat},
+    notation::{self, Notation},
+    solver::{self, Form, Method},
+    truth_table::TruthTable,
+};
+
+/// Minimize boolean functions from the command line
+///
+/// Prints the truth table, the K-map and the minimized forms of each
+/// function. Without arguments `karny` opens the editor when built with the
+/// `gui` feature.
+#[derive(Debug, Parser)]
+#[command(name = "karny", version)]
+struct Args {
+    /// An expression such as "A'B + C", a canonical list such as
+    /// "Σm(1,3) + d(2)" or "prod M(0,4)", or a minterm list such as "1,3,5"
+    /// with --minterms. Read from stdin, one function per line, when
+    /// omitted or "-"
+    input: Option<String>,
+
+    /// Read the input as a list of minterms instead of an expression
+    #[arg(short, long)]
+    minterms: bool,
+
+    /// Don't-care minterms for --minterms, e.g. "2,6"
+    #[arg(short, long, value_name = "LIST", requires = "minterms")]
+    dont_cares: Option<String>,
+
+    /// Comma separated input names, in most to least significant order
+    #[arg(long, value_name = "NAMES", value_delimiter = ',')]
+    vars: Vec<String>,
+
+    /// Name of the output
+    #[arg(long, value_name = "NAME", default_value = "F")]
+    output: String,
+
+    /// Which minimized forms to print
+    #[arg(long, value_enum, default_value_t = FormArg::Both)]
+    form: FormArg,
+
+    #[arg(long, value_enum, default_value_t = MethodArg::Auto)]
+    method: MethodArg,
+
+    #[arg(long, value_enum, default_value_t = NotationArg::Prime)]
+    notation: NotationArg,
+
+    /// Also print static hazards of each cover and a hazard-free version
+    #[arg(long)]
+    hazards: bool,
+
+    /// Don't print the truth table
+    #[arg(long)]
+    no_table: bool,
+
+    /// Don't print the K-map
+    #[arg(long)]
+    no_map: bool,
+
+    /// Print 5- and 6-input K-maps as stacked 4×4 sub-maps
+    #[arg(long)]
+    stacked: bool,
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum FormArg {
+    Sop,
+    Pos,
+    Both,
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum MethodArg {
+    Auto,
+    Exact,
+    Heuristic,
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum NotationArg {
+    Prime,
+    C,
+    Unicode,
+    Latex,
+    Verilog,
+    Python,
+}
+
+impl FormArg {
+    fn forms(self) -> &'static [Form] {
+        match self {
+            FormArg::Sop => &[Form::Sop],
+            FormArg::Pos => &[Form::Pos],
+            FormArg::Both => &[Form::Sop, Form::Pos],
+        }
+    }
+}
+
+impl From<MethodArg> for Method {
+    fn from(arg: MethodArg) -> Self {
+        match arg {
+            MethodArg::Auto => Method::Auto,
+            MethodArg::Exact => Method::Exact,
+            MethodArg::Heuristic => Method::Heuristic,
+        }
+    }
+}
+
+impl From<NotationArg> for Notation {
+    fn from(arg: NotationArg) -> Self {
+        match arg {
+            NotationArg::Prime => Notation::Prime,
+            NotationArg::C => Notation::C,
+            NotationArg::Unicode => Notation::Unicode,
+            NotationArg::Latex => Notation::Latex,
+            NotationArg::Verilog => Notation::Verilog,
+            NotationArg::Python => Notation::Python,
+        }
+    }
+}
+
+/// Runs the command line interface, exiting with 1 if any function fails
+pub fn run() -> ExitCode {
+    let args = Args::parse();
+
+    let inputs = match args.input.as_deref() {
+        Some(input) if input != "-" => vec![input.to_string()],
+        _ => {
+            let mut stdin = String::new();
+            if let Err(err) = io::stdin().read_to_string(&mut stdin) {
+                eprintln!("error: could not read stdin: {err}");
+                return ExitCode::FAILURE;
+            }
+            stdin
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty())
+                .map(str::to_string)
+                .collect()
+        }
+    };
+
+    let mut status = ExitCode::SUCCESS;
+    for (i, input) in inputs.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+
+        match build_table(&args, input) {
+            Ok(table) => print_report(&args, &table),
+            Err(err) => {
+                eprintln!("error: {err}");
+                status = ExitCode::FAILURE;
+            }
+        }
+    }
+    status
+}
+
+fn build_table(args: &Args, input: &str) -> Result<TruthTable, String> {
+    if !args.minterms {
+        if Canonical::looks_like(input) {
+            let mut canonical = Canonical::parse(input).map_err(|err| err.to_string())?;
+            if canonical.variables.is_empty() {
+                canonical.variables = args.vars.clone();
+            }
+            canonical.output.get_or_insert_with(|| args.output.clone());
+            return canonical.to_table().map_err(|err| err.to_string());
+        }
+
+        let expr =
+            Expression::parse_with(input, &args.vars).map_err(|err| underline(input, &err))?;
+        return expr.to_table(&args.output).map_err(|err| err.to_string());
+    }
+
+    // Lists given as flags are checked like written ones
+    let canonical = Canonical {
+        output: Some(args.output.clone()),
+        variables: args.vars.clone(),
+        kind: TermKind::Minterms,
+        terms: parse_list(input)?,
+        dont_cares: parse_list(args.dont_cares.as_deref().unwrap_or(""))?,
+    };
+    canonical.to_table().map_err(|err| err.to_string())
+}
+
+/// Parses minterm numbers separated by commas or whitespace
+fn parse_list(list: &str) -> Result<Vec<u32>, String> {
+    list.split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|s| !s.is_empty())
+        .map(|s| {
+            s.parse()
+                .map_err(|_| format!("'{s}' is not a minterm number"))
+        })
+        .collect()
+}
+
+/// Formats a parse error with the source line and a marker under the span
+fn underline(src: &str, err: &ParseError) -> String {
+    let start = src[..err.span.start].chars().count();
+    let width = src[err.span.start..err.span.end].chars().count().max(1);
+    format!(
+        "{err}\n  {src}\n  {}{}",
+        " ".repeat(start),
+        "^".repeat(width)
+    )
+}
+
+fn print_report(args: &Args, table: &TruthTable) {
+    if !args.no_table {
+        println!("{table}");
+    }
+
+    if !args.no_map {
+        let format = match KMapFormat::stacked(&table.inputs) {
+            Ok(stacked) if args.stacked => stacked,
+            _ => KMapFormat::auto(&table.inputs),
+        };
+        match KMap::from_table(table, format, Some(0)) {
+            Ok(kmap) => println!("{kmap}"),
+            Err(err) => eprintln!("warning: no K-map: {err}"),
+        }
+    }
+
+    for &form in args.form.forms() {
+        let kind = match form {
+            Form::Sop => TermKind::Minterms,
+            Form::Pos => TermKind::Maxterms,
+        };
+        println!("{}", Canonical::from_table(table, 0, kind));
+    }
+
+    for &form in args.form.forms() {
+        let solution = solver::minimize_with(table, 0, form, args.method.into());
+        let label = match form {
+            Form::Sop => "SOP",
+            Form::Pos => "POS",
+        };
+        if solution.method == Method::Heuristic {
+            eprintln!("note: the {label} cover comes from the heuristic and may not be minimal");
+        }
+        println!(
+            "{label}: {} = {}",
+            table.outputs[0],
+            notation::format_solution(&solution, &table.inputs, args.notation.into())
+        );
+
+        if args.hazards {
+            let hazards = solution.hazards();
+            for hazard in &hazards {
+                println!(
+                    "  hazard: {} -> {} while {} changes",
+                    hazard.from, hazard.to, table.inputs[hazard.variable]
+                );
+            }
+            if !hazards.is_empty() {
+                println!(
+                    "{label} (hazard-free): {} = {}",
+                    table.outputs[0],
+                    notation::format_terms(
+                        &solution.hazard_free_cover(&hazards),
+                        form,
+                        &table.inputs,
+                        args.notation.into()
+                    )
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+fn parse_args(argv: &[&str]) -> Args {
+    Args::try_parse_from(std::iter::once("karny").chain(argv.iter().copied())).unwrap()
+}
+
+#[test]
+fn builds_tables_from_minterm_lists() {
+    use crate::logic::variable::BitValue;
+
+    let args = parse_args(&["-m", "-d", "2", "1 3,5"]);
+    let table = build_table(&args, args.input.as_deref().unwrap()).unwrap();
+
+    assert_eq!(table.inputs, vec!["A", "B", "C"]);
+    assert_eq!(table.terms(0, BitValue::One), vec![1, 3, 5]);
+    assert_eq!(table.terms(0, BitValue::DontCare), vec![2]);
+
+    let named = parse_args(&["-m", "--vars", "X,Y", "--output", "G", "3"]);
+    let table = build_table(&named, "3").unwrap();
+    assert_eq!(table.inputs, vec!["X", "Y"]);
+    assert_eq!(table.outputs, vec!["G"]);
+
+    let short = parse_args(&["-m", "--vars", "X", "3"]);
+    assert!(build_table(&short, "3").is_err());
+    assert!(build_table(&args, "1, two").is_err());
+    assert_eq!(
+        build_table(&args, "1 2").unwrap_err(),
+        "2 is listed both as a term and as a don't care"
+    );
+}
+
+#[test]
+fn builds_tables_from_canonical_lists() {
+    use crate::logic::variable::BitValue;
+
+    let args = parse_args(&["G(X,Y,Z) = ΠM(0,4) + d(5)"]);
+    let table = build_table(&args, args.input.as_deref().unwrap()).unwrap();
+    assert_eq!(table.inputs, vec!["X", "Y", "Z"]);
+    assert_eq!(table.outputs, vec!["G"]);
+    assert_eq!(table.terms(0, BitValue::Zero), vec![0, 4]);
+
+    // Without a header the flags name the function
+    let args = parse_args(&["--vars", "P,Q", "sum m(3)"]);
+    let table = build_table(&args, "sum m(3)").unwrap();
+    assert_eq!(table.inputs, vec!["P", "Q"]);
+    assert_eq!(table.outputs, vec!["F"]);
+    assert!(build_table(&args, "sum m(4)").is_err());
+
+    // A broken list is reported as such, not as a bad expression
+    assert_eq!(
+        build_table(&args, "Σm(1,3").unwrap_err(),
+        "expected ')' at 7"
+    );
+}
+
+#[test]
+fn underlines_expression_errors() {
+    let args = parse_args(&["A + $"]);
+    let err = build_table(&args, "A + $").unwrap_err();
+    assert!(err.ends_with("\n  A + $\n      ^"));
+
+    // Known names keep their order ahead of the rest
+    let args = parse_args(&["--vars", "C,A", "A + B"]);
+    let table = build_table(&args, "A + B").unwrap();
+    assert_eq!(table.inputs, vec!["C", "A", "B"]);
+}
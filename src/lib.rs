@@ -0,0 +1,23 @@
+//! Boolean function minimization and Karnaugh maps
+//!
+//! The [`logic`] module is the library API, it has no GUI dependencies
+//! when the crate is built with `default-features = false`:
+//!
+//! - [`logic::truth_table`]: Truth tables with don't-care outputs
+//! - [`logic::expression`]: Parsing boolean expressions into truth tables
+//! - [`logic::solver`]: Exact and heuristic SOP/POS minimization
+//! - [`logic::kmap`]: Karnaugh map layouts and implicant rectangles
+//! - [`logic::gray`]: Gray code helpers shared by the maps
+//! - [`logic::notation`]: Printing minimized covers in several syntaxes
+//!
+//! ### Examples
+//! ```
+//! use karny::logic::{expression::Expression, notation, solver};
+//!
+//! let table = Expression::parse("A'B + AB + C").unwrap().to_table("F").unwrap();
+//! let solution = solver::minimize(&table, 0, solver::Form::Sop);
+//! let text = notation::format_solution(&solution, &table.inputs, Default::default());
+//! assert_eq!(text, "C + B");
+//! ```
+
+pub mod logic;
@@ -0,0 +1,562 @@
+use std::{fmt, str::FromStr};
+
+use crate::logic::{
+    gray::{self, SizeError},
+    truth_table::TruthTable,
+    variable::BitValue,
+};
+
+/// Whether the listed terms are where the function is 1 or where it is 0
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum TermKind {
+    /// `Σm(...)`, rows where the output is 1
+    #[default]
+    Minterms,
+    /// `ΠM(...)`, rows where the output is 0
+    Maxterms,
+}
+
+impl TermKind {
+    /// The output value of a listed row
+    pub fn value(self) -> BitValue {
+        match self {
+            TermKind::Minterms => BitValue::One,
+            TermKind::Maxterms => BitValue::Zero,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CanonicalError {
+    /// The text doesn't follow the notation, `at` is a byte offset
+    Syntax {
+        message: String,
+        at: usize,
+    },
+    /// A row is listed both as a term and as a don't care
+    Overlap(u32),
+    /// A row number needs more variables than the function has
+    OutOfRange {
+        term: u32,
+        variables: usize,
+    },
+    /// The named variables don't match the table being filled
+    VariableCount {
+        expected: usize,
+        found: usize,
+    },
+    Size(SizeError),
+}
+
+impl From<SizeError> for CanonicalError {
+    fn from(err: SizeError) -> Self {
+        CanonicalError::Size(err)
+    }
+}
+
+impl fmt::Display for CanonicalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CanonicalError::Syntax { message, at } => write!(f, "{message} at {at}"),
+            CanonicalError::Overlap(term) => {
+                write!(f, "{term} is listed both as a term and as a don't care")
+            }
+            CanonicalError::OutOfRange { term, variables } => {
+                write!(f, "{term} does not fit in {variables} variables")
+            }
+            CanonicalError::VariableCount { expected, found } => {
+                write!(f, "expected {expected} variables, found {found}")
+            }
+            CanonicalError::Size(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl std::error::Error for CanonicalError {}
+
+/// A function written as a list of row numbers, e.g.
+/// `F(A,B,C,D) = Σm(1,3,7,11) + d(0,2)` or `ΠM(0,4) + d(5)`
+///
+/// The `F(A,B,...) =` header is optional. `sum` and `prod` may be written
+/// in place of `Σ` and `Π`, and a bare `m(...)` or `M(...)` picks the kind
+/// by case.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Canonical {
+    /// Output name from the header
+    pub output: Option<String>,
+    /// Variables from the header, first is the most significant bit
+    pub variables: Vec<String>,
+    pub kind: TermKind,
+    /// Sorted row numbers
+    pub terms: Vec<u32>,
+    /// Sorted row numbers of don't cares
+    pub dont_cares: Vec<u32>,
+}
+
+impl Canonical {
+    /// Parses the notation
+    ///
+    /// ### Examples
+    /// ```
+    /// # use karny::logic::canonical::{Canonical, TermKind};
+    /// let f = Canonical::parse("F(A,B,C) = sum m(1, 3, 7) + d(0)").unwrap();
+    /// assert_eq!(f.variables, vec!["A", "B", "C"]);
+    /// assert_eq!(f.terms, vec![1, 3, 7]);
+    ///
+    /// let g = Canonical::parse("ΠM(0,4)").unwrap();
+    /// assert_eq!(g.kind, TermKind::Maxterms);
+    /// ```
+    pub fn parse(src: &str) -> Result<Self, CanonicalError> {
+        Scanner::new(src).canonical()
+    }
+
+    /// Whether `src` starts like a canonical form, an optional header and
+    /// then `Σ`, `Π`, `sum`, `prod`, `m(` or `M(`
+    ///
+    /// Lets callers that also accept expressions report the canonical
+    /// parse error instead of trying the expression parser.
+    ///
+    /// ### Examples
+    /// ```
+    /// # use karny::logic::canonical::Canonical;
+    /// assert!(Canonical::looks_like("F = Σm(1, 3"));
+    /// assert!(Canonical::looks_like("m(1, 3, 2^4)"));
+    /// assert!(!Canonical::looks_like("A'B + m"));
+    /// ```
+    pub fn looks_like(src: &str) -> bool {
+        let mut scanner = Scanner::new(src);
+        let _ = scanner.header();
+        let start = scanner.pos;
+        match scanner.kind() {
+            // A lone `m` or `M` is only a canonical form when a list follows
+            Ok(_) => {
+                let word = src[start..scanner.pos].trim();
+                !matches!(word, "m" | "M") || scanner.peek() == Some('(')
+            }
+            Err(_) => false,
+        }
+    }
+
+    /// Describes one output of a table, listing the rows equal to
+    /// `kind.value()` and the don't cares
+    pub fn from_table(table: &TruthTable, output_index: usize, kind: TermKind) -> Self {
+        Self {
+            output: table.outputs.get(output_index).cloned(),
+            variables: table.inputs.clone(),
+            kind,
+            terms: table.terms(output_index, kind.value()),
+            dont_cares: table.terms(output_index, BitValue::DontCare),
+        }
+    }
+
+    /// Overwrites one output of `table` with this function
+    ///
+    /// Rows that are neither listed nor don't cares get the opposite value
+    /// of the listed ones.
+    pub fn fill(&self, table: &mut TruthTable, output_index: usize) -> Result<(), CanonicalError> {
+        let n = table.inputs.len();
+        if !self.variables.is_empty() && self.variables.len() != n {
+            return Err(CanonicalError::VariableCount {
+                expected: n,
+                found: self.variables.len(),
+            });
+        }
+        self.check_range(n)?;
+        self.check_overlap()?;
+
+        let (listed, rest) = match self.kind {
+            TermKind::Minterms => (BitValue::One, BitValue::Zero),
+            TermKind::Maxterms => (BitValue::Zero, BitValue::One),
+        };
+        for row in 0..table.rows.len() {
+            table.set(row, output_index, rest);
+        }
+        for &term in &self.terms {
+            table.set(term as usize, output_index, listed);
+        }
+        for &term in &self.dont_cares {
+            table.set(term as usize, output_index, BitValue::DontCare);
+        }
+        Ok(())
+    }
+
+    /// Builds a single-output table
+    ///
+    /// Without header variables the table gets just enough inputs for the
+    /// largest row number, named `A`, `B`, ...
+    pub fn to_table(&self) -> Result<TruthTable, CanonicalError> {
+        let variables = if self.variables.is_empty() {
+            let highest = self.terms.iter().chain(&self.dont_cares).max().copied();
+            let needed = highest.map_or(1, |h| (u32::BITS - h.leading_zeros()).max(1) as usize);
+            gray::check_size(needed)?;
+            (0..needed)
+                .map(|i| ((b'A' + i as u8) as char).to_string())
+                .collect()
+        } else {
+            self.variables.clone()
+        };
+
+        let output = self.output.as_deref().unwrap_or("F");
+        let mut table = TruthTable::try_new(&variables, &[output])?;
+        self.fill(&mut table, 0)?;
+        Ok(table)
+    }
+
+    /// Formats with `sum`/`prod` instead of `Σ`/`Π`
+    pub fn to_ascii(&self) -> String {
+        let mut out = String::new();
+        self.write(&mut out, true)
+            .expect("writing to a String cannot fail");
+        out
+    }
+
+    fn check_range(&self, variables: usize) -> Result<(), CanonicalError> {
+        gray::check_size(variables)?;
+        let rows = 1u64 << variables;
+        match self
+            .terms
+            .iter()
+            .chain(&self.dont_cares)
+            .find(|&&t| u64::from(t) >= rows)
+        {
+            Some(&term) => Err(CanonicalError::OutOfRange { term, variables }),
+            None => Ok(()),
+        }
+    }
+
+    fn check_overlap(&self) -> Result<(), CanonicalError> {
+        match self.terms.iter().find(|t| self.dont_cares.contains(t)) {
+            Some(&term) => Err(CanonicalError::Overlap(term)),
+            None => Ok(()),
+        }
+    }
+
+    fn write(&self, f: &mut impl fmt::Write, ascii: bool) -> fmt::Result {
+        let list = |terms: &[u32]| {
+            terms
+                .iter()
+                .map(u32::to_string)
+                .collect::<Vec<_>>()
+                .join(",")
+        };
+
+        if let Some(output) = &self.output {
+            if self.variables.is_empty() {
+                write!(f, "{output} = ")?;
+            } else {
+                write!(f, "{output}({}) = ", self.variables.join(","))?;
+            }
+        }
+
+        let op = match (self.kind, ascii) {
+            (TermKind::Minterms, false) => "Σm",
+            (TermKind::Minterms, true) => "sum m",
+            (TermKind::Maxterms, false) => "ΠM",
+            (TermKind::Maxterms, true) => "prod M",
+        };
+        write!(f, "{op}({})", list(&self.terms))?;
+
+        if !self.dont_cares.is_empty() {
+            write!(f, " + d({})", list(&self.dont_cares))?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Canonical {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write(f, false)
+    }
+}
+
+impl FromStr for Canonical {
+    type Err = CanonicalError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Canonical::parse(s)
+    }
+}
+
+struct Scanner<'a> {
+    src: &'a str,
+    pos: usize,
+}
+
+impl<'a> Scanner<'a> {
+    fn new(src: &'a str) -> Self {
+        Self { src, pos: 0 }
+    }
+
+    fn error<T>(&self, message: &str) -> Result<T, CanonicalError> {
+        Err(CanonicalError::Syntax {
+            message: message.to_string(),
+            at: self.pos,
+        })
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        let rest = &self.src[self.pos..];
+        self.pos += rest.len() - rest.trim_start().len();
+        self.src[self.pos..].chars().next()
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.pos += c.len_utf8();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), CanonicalError> {
+        if self.eat(c) {
+            Ok(())
+        } else {
+            self.error(&format!("expected '{c}'"))
+        }
+    }
+
+    /// A name made of letters, digits and `_`, starting with a letter
+    fn ident(&mut self) -> Option<&'a str> {
+        if !self.peek()?.is_alphabetic() {
+            return None;
+        }
+
+        let rest = &self.src[self.pos..];
+        let len = rest
+            .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .unwrap_or(rest.len());
+        self.pos += len;
+        Some(&rest[..len])
+    }
+
+    fn number(&mut self) -> Result<u32, CanonicalError> {
+        self.peek();
+        let rest = &self.src[self.pos..];
+        let len = rest
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(rest.len());
+        if len == 0 {
+            return self.error("expected a row number");
+        }
+
+        let value = rest[..len]
+            .parse()
+            .or_else(|_| self.error("row number is too large"))?;
+        self.pos += len;
+        Ok(value)
+    }
+
+    /// `(1, 3, 7)`, commas are optional
+    fn list(&mut self) -> Result<Vec<u32>, CanonicalError> {
+        self.expect('(')?;
+        let mut terms = Vec::new();
+        while !self.eat(')') {
+            if self.peek().is_none() {
+                return self.error("expected ')'");
+            }
+            terms.push(self.number()?);
+            self.eat(',');
+        }
+        terms.sort_unstable();
+        terms.dedup();
+        Ok(terms)
+    }
+
+    /// `F(A,B,C) =` or `F =`, restoring the position when absent
+    fn header(&mut self) -> Result<Option<(String, Vec<String>)>, CanonicalError> {
+        let start = self.pos;
+        let parsed = (|| {
+            let name = self.ident()?;
+            let mut variables = Vec::new();
+            if self.eat('(') {
+                while !self.eat(')') {
+                    variables.push(self.ident()?.to_string());
+                    self.eat(',');
+                }
+            }
+            self.eat('=').then(|| (name.to_string(), variables))
+        })();
+
+        if parsed.is_none() {
+            self.pos = start;
+        }
+        Ok(parsed)
+    }
+
+    fn kind(&mut self) -> Result<TermKind, CanonicalError> {
+        let symbol = match self.peek() {
+            Some('Σ' | '∑') => Some(TermKind::Minterms),
+            Some('Π' | '∏') => Some(TermKind::Maxterms),
+            _ => None,
+        };
+        if let Some(kind) = symbol {
+            self.pos += self.peek().map_or(0, char::len_utf8);
+            let start = self.pos;
+            if !matches!(self.ident(), Some("m" | "M")) {
+                self.pos = start;
+            }
+            return Ok(kind);
+        }
+
+        let start = self.pos;
+        let kind = match self.ident() {
+            Some(word) if word.eq_ignore_ascii_case("sum") => TermKind::Minterms,
+            Some(word)
+                if word.eq_ignore_ascii_case("prod") || word.eq_ignore_ascii_case("product") =>
+            {
+                TermKind::Maxterms
+            }
+            Some("m") => return Ok(TermKind::Minterms),
+            Some("M") => return Ok(TermKind::Maxterms),
+            _ => {
+                self.pos = start;
+                return self.error("expected Σm, ΠM, sum or prod");
+            }
+        };
+
+        let start = self.pos;
+        if !matches!(self.ident(), Some("m" | "M")) {
+            self.pos = start;
+        }
+        Ok(kind)
+    }
+
+    /// `+ d(0, 2)`, also accepting `·`, `*`, `.` or `,` before the list
+    fn dont_cares(&mut self) -> Result<Vec<u32>, CanonicalError> {
+        let joined = ['+', '·', '*', '.', ','].into_iter().any(|c| self.eat(c));
+        let start = self.pos;
+
+        match self.ident() {
+            Some("d" | "D" | "dc" | "DC") => self.list(),
+            _ if joined => {
+                self.pos = start;
+                self.error("expected d(...)")
+            }
+            _ => {
+                self.pos = start;
+                Ok(Vec::new())
+            }
+        }
+    }
+
+    fn canonical(&mut self) -> Result<Canonical, CanonicalError> {
+        let (output, variables) = match self.header()? {
+            Some((output, variables)) => (Some(output), variables),
+            None => (None, Vec::new()),
+        };
+
+        let kind = self.kind()?;
+        let terms = self.list()?;
+        let dont_cares = self.dont_cares()?;
+
+        if self.peek().is_some() {
+            return self.error("unexpected text");
+        }
+        let canonical = Canonical {
+            output,
+            variables,
+            kind,
+            terms,
+            dont_cares,
+        };
+        canonical.check_overlap()?;
+        if !canonical.variables.is_empty() {
+            canonical.check_range(canonical.variables.len())?;
+        }
+        Ok(canonical)
+    }
+}
+
+#[test]
+fn parses_unicode_and_ascii_spellings() {
+    let expected = Canonical {
+        output: Some("F".to_string()),
+        variables: ["A", "B", "C", "D"].map(String::from).to_vec(),
+        kind: TermKind::Minterms,
+        terms: vec![1, 3, 7, 11],
+        dont_cares: vec![0, 2],
+    };
+
+    for src in [
+        "F(A,B,C,D) = Σm(1,3,7,11) + d(0,2)",
+        "F(A, B, C, D) = ∑ m(11, 7, 3, 1) + d(2, 0)",
+        "F(A,B,C,D)=sum m(1 3 7 11)+dc(0 2)",
+        "F(A,B,C,D) = SUM(1,3,7,11), d(0,2)",
+    ] {
+        assert_eq!(Canonical::parse(src).unwrap(), expected, "{src}");
+    }
+
+    let max = Canonical::parse("ΠM(0, 4) · D(5)").unwrap();
+    assert_eq!(max.kind, TermKind::Maxterms);
+    assert_eq!((max.terms, max.dont_cares), (vec![0, 4], vec![5]));
+    assert_eq!(
+        Canonical::parse("prod(0)").unwrap().kind,
+        TermKind::Maxterms
+    );
+    assert_eq!(Canonical::parse("M(0)").unwrap().kind, TermKind::Maxterms);
+    assert_eq!(Canonical::parse("m(0)").unwrap().kind, TermKind::Minterms);
+}
+
+#[test]
+fn reports_invalid_lists() {
+    assert!(matches!(
+        Canonical::parse("Σm(1,3"),
+        Err(CanonicalError::Syntax { .. })
+    ));
+    assert!(matches!(
+        Canonical::parse("Σm(1) + (2)"),
+        Err(CanonicalError::Syntax { at: 8, .. })
+    ));
+    assert_eq!(
+        Canonical::parse("Σm(1,2) + d(2)").unwrap_err(),
+        CanonicalError::Overlap(2)
+    );
+    assert_eq!(
+        Canonical::parse("F(A,B) = Σm(4)").unwrap_err(),
+        CanonicalError::OutOfRange {
+            term: 4,
+            variables: 2
+        }
+    );
+
+    // Too many header variables to even count the rows
+    let names: Vec<String> = (0..64).map(|i| format!("X{i}")).collect();
+    let src = format!("F({}) = Σm(1)", names.join(","));
+    assert_eq!(
+        Canonical::parse(&src).unwrap_err(),
+        CanonicalError::Size(SizeError { variables: 64 })
+    );
+}
+
+#[test]
+fn round_trips_through_tables() {
+    let f = Canonical::parse("ΠM(0,4) + d(5)").unwrap();
+    let table = f.to_table().unwrap();
+    assert_eq!(table.inputs, vec!["A", "B", "C"]);
+    assert_eq!(table.terms(0, BitValue::One), vec![1, 2, 3, 6, 7]);
+
+    let min = Canonical::from_table(&table, 0, TermKind::Minterms);
+    assert_eq!(min.to_string(), "F(A,B,C) = Σm(1,2,3,6,7) + d(5)");
+    assert_eq!(min.to_ascii(), "F(A,B,C) = sum m(1,2,3,6,7) + d(5)");
+
+    let max = Canonical::from_table(&table, 0, TermKind::Maxterms);
+    assert_eq!(max.to_string(), "F(A,B,C) = ΠM(0,4) + d(5)");
+    assert_eq!(Canonical::parse(&max.to_ascii()).unwrap(), max);
+
+    let mut wide = TruthTable::new(&["A", "B", "C", "D"], &["G"]);
+    assert_eq!(
+        f.fill(&mut wide, 0),
+        Ok(()),
+        "a header-less list fits any table wide enough"
+    );
+    let named = Canonical::parse("F(A,B) = Σm(1)").unwrap();
+    assert_eq!(
+        named.fill(&mut wide, 0).unwrap_err(),
+        CanonicalError::VariableCount {
+            expected: 4,
+            found: 2
+        }
+    );
+}
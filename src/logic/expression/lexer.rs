@@ -0,0 +1,181 @@
+use super::{ParseError, ParseErrorKind, Span};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TokenKind {
+    Ident(String),
+    Const(bool),
+
+    Not,
+    Prime,
+    And,
+    Or,
+    Xor,
+    Xnor,
+
+    LParen,
+    RParen,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Token {
+    pub kind: TokenKind,
+    pub span: Span,
+}
+
+/// Splits the source into tokens
+///
+/// Words are matched against the operator keywords first, then against
+/// `known` variable names. Any other word is split into one variable per
+/// letter so that `AB` means `A·B`; digits stay attached to the letter before
+/// them (`A1B2` is `A1·B2`) and words containing `_` are kept whole.
+///
+/// ### Parameters
+/// - `src`: The expression source
+/// - `known`: Variable names that should never be split
+pub fn tokenize(src: &str, known: &[String]) -> Result<Vec<Token>, ParseError> {
+    let mut tokens = Vec::new();
+    let mut chars = src.char_indices().peekable();
+
+    while let Some((start, c)) = chars.next() {
+        let single = |kind: TokenKind| Token {
+            kind,
+            span: Span::new(start, start + c.len_utf8()),
+        };
+
+        match c {
+            c if c.is_whitespace() => {}
+
+            '(' => tokens.push(single(TokenKind::LParen)),
+            ')' => tokens.push(single(TokenKind::RParen)),
+            '\'' | '’' => tokens.push(single(TokenKind::Prime)),
+            '!' | '~' | '¬' => tokens.push(single(TokenKind::Not)),
+            '*' | '·' | '.' | '∧' => tokens.push(single(TokenKind::And)),
+            '+' | '∨' => tokens.push(single(TokenKind::Or)),
+            '^' | '⊕' => tokens.push(single(TokenKind::Xor)),
+            '⊙' => tokens.push(single(TokenKind::Xnor)),
+            '0' => tokens.push(single(TokenKind::Const(false))),
+            '1' => tokens.push(single(TokenKind::Const(true))),
+
+            '&' | '|' => {
+                let mut end = start + 1;
+                if chars.next_if(|&(_, n)| n == c).is_some() {
+                    end += 1;
+                }
+
+                let kind = if c == '&' {
+                    TokenKind::And
+                } else {
+                    TokenKind::Or
+                };
+                tokens.push(Token {
+                    kind,
+                    span: Span::new(start, end),
+                });
+            }
+
+            c if c.is_alphabetic() || c == '_' => {
+                let mut end = start + c.len_utf8();
+                while let Some(&(i, n)) = chars.peek() {
+                    // Other numeric characters such as `²` are not part of a
+                    // name and are reported as unexpected below
+                    if !(n.is_alphabetic() || n.is_ascii_digit() || n == '_') {
+                        break;
+                    }
+                    end = i + n.len_utf8();
+                    chars.next();
+                }
+
+                word_tokens(&src[start..end], start, known, &mut tokens);
+            }
+
+            _ => {
+                return Err(ParseError {
+                    kind: ParseErrorKind::UnexpectedChar(c),
+                    span: Span::new(start, start + c.len_utf8()),
+                });
+            }
+        }
+    }
+
+    Ok(tokens)
+}
+
+fn word_tokens(word: &str, offset: usize, known: &[String], tokens: &mut Vec<Token>) {
+    let keyword = match word.to_ascii_lowercase().as_str() {
+        "and" => Some(TokenKind::And),
+        "or" => Some(TokenKind::Or),
+        "not" => Some(TokenKind::Not),
+        "xor" => Some(TokenKind::Xor),
+        "xnor" => Some(TokenKind::Xnor),
+        _ => None,
+    };
+
+    if let Some(kind) = keyword {
+        tokens.push(Token {
+            kind,
+            span: Span::new(offset, offset + word.len()),
+        });
+        return;
+    }
+
+    if word.contains('_') || known.iter().any(|k| k == word) {
+        tokens.push(Token {
+            kind: TokenKind::Ident(word.to_string()),
+            span: Span::new(offset, offset + word.len()),
+        });
+        return;
+    }
+
+    // A letter followed by any digits forms one variable
+    let mut start = 0;
+    let indices: Vec<(usize, char)> = word.char_indices().collect();
+    for (n, &(i, c)) in indices.iter().enumerate() {
+        let next_is_letter = indices
+            .get(n + 1)
+            .is_none_or(|&(_, next)| next.is_alphabetic());
+
+        if (c.is_alphabetic() || c.is_ascii_digit()) && next_is_letter {
+            let end = i + c.len_utf8();
+            tokens.push(Token {
+                kind: TokenKind::Ident(word[start..end].to_string()),
+                span: Span::new(offset + start, offset + end),
+            });
+            start = end;
+        }
+    }
+}
+
+#[test]
+fn splits_juxtaposed_letters() {
+    let kinds: Vec<TokenKind> = tokenize("AB1c", &[])
+        .unwrap()
+        .into_iter()
+        .map(|t| t.kind)
+        .collect();
+
+    assert_eq!(
+        kinds,
+        vec![
+            TokenKind::Ident("A".into()),
+            TokenKind::Ident("B1".into()),
+            TokenKind::Ident("c".into()),
+        ]
+    );
+}
+
+#[test]
+fn keeps_known_and_underscored_names() {
+    let tokens = tokenize("Cin xor c_out", &["Cin".to_string()]).unwrap();
+
+    assert_eq!(tokens[0].kind, TokenKind::Ident("Cin".into()));
+    assert_eq!(tokens[1].kind, TokenKind::Xor);
+    assert_eq!(tokens[2].kind, TokenKind::Ident("c_out".into()));
+    assert_eq!(tokens[2].span, Span::new(8, 13));
+}
+
+#[test]
+fn rejects_non_ascii_digits_in_names() {
+    let err = tokenize("A² + B", &[]).unwrap_err();
+    assert_eq!(err.kind, ParseErrorKind::UnexpectedChar('²'));
+    assert_eq!(err.span, Span::new(1, 3));
+}
@@ -0,0 +1,273 @@
+mod lexer;
+mod parser;
+
+use std::{fmt, str::FromStr};
+
+use crate::logic::{gray::SizeError, truth_table::TruthTable, variable::BitValue};
+
+/// Byte range into the expression source, used to underline errors
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Span {
+    pub fn new(start: usize, end: usize) -> Self {
+        Self { start, end }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    Empty,
+    UnexpectedChar(char),
+    UnexpectedToken,
+    UnexpectedEnd,
+    UnclosedParen,
+    UnmatchedParen,
+    /// Parentheses or `!` nested deeper than the parser descends
+    TooDeep,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub kind: ParseErrorKind,
+    pub span: Span,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            ParseErrorKind::Empty => write!(f, "expression is empty"),
+            ParseErrorKind::UnexpectedChar(c) => {
+                write!(f, "unexpected character '{c}' at {}", self.span.start)
+            }
+            ParseErrorKind::UnexpectedToken => {
+                write!(f, "unexpected token at {}", self.span.start)
+            }
+            ParseErrorKind::UnexpectedEnd => write!(f, "expression ends unexpectedly"),
+            ParseErrorKind::UnclosedParen => {
+                write!(f, "parenthesis at {} is never closed", self.span.start)
+            }
+            ParseErrorKind::UnmatchedParen => {
+                write!(f, "unmatched ')' at {}", self.span.start)
+            }
+            ParseErrorKind::TooDeep => {
+                write!(f, "expression nested too deeply at {}", self.span.start)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Syntax tree of a boolean expression, variables are indices into
+/// [`Expression::variables`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Expr {
+    Const(bool),
+    Var(usize),
+
+    Not(Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Xor(Box<Expr>, Box<Expr>),
+}
+
+impl Expr {
+    /// Evaluates the tree with `values[i]` as the value of variable `i`
+    pub fn eval(&self, values: &[bool]) -> bool {
+        match self {
+            Expr::Const(v) => *v,
+            Expr::Var(i) => values[*i],
+            Expr::Not(e) => !e.eval(values),
+            Expr::And(a, b) => a.eval(values) && b.eval(values),
+            Expr::Or(a, b) => a.eval(values) || b.eval(values),
+            Expr::Xor(a, b) => a.eval(values) ^ b.eval(values),
+        }
+    }
+
+    fn remap(&mut self, order: &[usize]) {
+        match self {
+            Expr::Const(_) => {}
+            Expr::Var(i) => *i = order[*i],
+            Expr::Not(e) => e.remap(order),
+            Expr::And(a, b) | Expr::Or(a, b) | Expr::Xor(a, b) => {
+                a.remap(order);
+                b.remap(order);
+            }
+        }
+    }
+}
+
+/// A parsed boolean expression
+///
+/// Accepts prime notation (`A'B + C`), C-style operators (`!A & B | C`),
+/// Unicode logic symbols (`¬A∧B∨C`) and the keywords `and`, `or`, `not`,
+/// `xor` and `xnor`. Juxtaposed operands are ANDed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Expression {
+    pub variables: Vec<String>,
+    pub root: Expr,
+}
+
+impl Expression {
+    /// Parses an expression, ordering its variables alphabetically
+    ///
+    /// ### Examples
+    /// ```
+    /// # use karny::logic::expression::Expression;
+    /// let expr = Expression::parse("C + A'B").unwrap();
+    /// assert_eq!(expr.variables, vec!["A", "B", "C"]);
+    /// ```
+    pub fn parse(src: &str) -> Result<Self, ParseError> {
+        Self::parse_with(src, &[])
+    }
+
+    /// Parses an expression against an existing set of variable names
+    ///
+    /// Names in `known` are never split into single letters and keep their
+    /// order at the front of [`Expression::variables`], even when unused.
+    /// Any new names follow alphabetically.
+    ///
+    /// ### Parameters
+    /// - `src`: The expression source
+    /// - `known`: Variable names already in use, e.g. `TruthTable::inputs`
+    pub fn parse_with(src: &str, known: &[String]) -> Result<Self, ParseError> {
+        let tokens = lexer::tokenize(src, known)?;
+        let mut parser = parser::Parser::new(&tokens, src.len());
+        let mut root = parser.parse()?;
+
+        let mut extra: Vec<String> = parser
+            .variables
+            .iter()
+            .filter(|v| !known.contains(v))
+            .cloned()
+            .collect();
+        extra.sort();
+
+        let variables: Vec<String> = known.iter().cloned().chain(extra).collect();
+        let order: Vec<usize> = parser
+            .variables
+            .iter()
+            .map(|v| variables.iter().position(|n| n == v).unwrap())
+            .collect();
+        root.remap(&order);
+
+        Ok(Self { variables, root })
+    }
+
+    /// Evaluates the expression for a row number, first variable in the
+    /// most significant bit as in [`TruthTable`]
+    pub fn evaluate(&self, minterm: u32) -> bool {
+        let n = self.variables.len();
+        let values: Vec<bool> = (0..n).map(|i| (minterm >> (n - 1 - i)) & 1 == 1).collect();
+        self.root.eval(&values)
+    }
+
+    /// Builds a truth table with the expression's variables as inputs and
+    /// a single output named `output`
+    pub fn to_table(&self, output: &str) -> Result<TruthTable, SizeError> {
+        let mut table = TruthTable::try_new(&self.variables, &[output])?;
+        for row in 0..table.rows.len() {
+            let value = if self.evaluate(row as u32) {
+                BitValue::One
+            } else {
+                BitValue::Zero
+            };
+            table.set(row, 0, value);
+        }
+        Ok(table)
+    }
+}
+
+impl FromStr for Expression {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Expression::parse(s)
+    }
+}
+
+#[cfg(test)]
+fn ones(src: &str) -> Vec<u32> {
+    Expression::parse(src)
+        .unwrap()
+        .to_table("F")
+        .unwrap()
+        .terms(0, BitValue::One)
+}
+
+#[test]
+fn notations_agree() {
+    let expected = ones("A'B + C");
+    assert_eq!(expected, vec![1, 2, 3, 5, 7]);
+
+    assert_eq!(ones("!A & B | C"), expected);
+    assert_eq!(ones("¬A∧B∨C"), expected);
+    assert_eq!(ones("not A and B or C"), expected);
+    assert_eq!(ones("(A + B')' + C"), expected);
+}
+
+#[test]
+fn parses_xor_and_precedence() {
+    assert_eq!(ones("A xor B"), vec![1, 2]);
+    assert_eq!(ones("A ^ B xnor C"), ones("(A ⊕ B)' ⊕ C"));
+
+    // AND binds tighter than XOR, which binds tighter than OR
+    assert_eq!(ones("A + B ^ CD"), ones("A + (B ^ (C D))"));
+}
+
+#[test]
+fn keeps_known_variable_order() {
+    let known = vec!["Cin".to_string(), "A".to_string()];
+    let expr = Expression::parse_with("A Cin + B", &known).unwrap();
+
+    assert_eq!(expr.variables, vec!["Cin", "A", "B"]);
+    assert!(expr.evaluate(0b110));
+    assert!(!expr.evaluate(0b010));
+}
+
+#[test]
+fn errors_carry_spans() {
+    let err = Expression::parse("A + (B C").unwrap_err();
+    assert_eq!(err.kind, ParseErrorKind::UnclosedParen);
+    assert_eq!(err.span, Span::new(4, 5));
+
+    let err = Expression::parse("A + $").unwrap_err();
+    assert_eq!(err.kind, ParseErrorKind::UnexpectedChar('$'));
+    assert_eq!(err.span, Span::new(4, 5));
+
+    let err = Expression::parse("A +").unwrap_err();
+    assert_eq!(err.kind, ParseErrorKind::UnexpectedEnd);
+    assert_eq!(err.span, Span::new(3, 3));
+
+    let err = Expression::parse("A)").unwrap_err();
+    assert_eq!(err.kind, ParseErrorKind::UnmatchedParen);
+
+    assert_eq!(
+        Expression::parse("  ").unwrap_err().kind,
+        ParseErrorKind::Empty
+    );
+}
+
+#[test]
+fn rejects_deep_nesting() {
+    let err = Expression::parse(&"(".repeat(100_000)).unwrap_err();
+    assert_eq!(err.kind, ParseErrorKind::TooDeep);
+    assert_eq!(err.span, Span::new(256, 257));
+    assert_eq!(err.to_string(), "expression nested too deeply at 256");
+
+    let err = Expression::parse(&format!("{}A", "!".repeat(100_000))).unwrap_err();
+    assert_eq!(err.kind, ParseErrorKind::TooDeep);
+
+    let nested = format!("{}A{}", "(!".repeat(100), ")".repeat(100));
+    assert_eq!(ones(&nested), vec![1]);
+}
+
+#[test]
+fn rejects_oversized_tables() {
+    let expr = Expression::parse("ABCDEFGHIJKLMNOPQ").unwrap();
+    assert_eq!(expr.to_table("F").unwrap_err(), SizeError { variables: 17 });
+}
@@ -0,0 +1,185 @@
+use super::{
+    Expr, ParseError, ParseErrorKind, Span,
+    lexer::{Token, TokenKind},
+};
+
+/// Most parentheses and prefix `!` the parser descends into, deeper input
+/// would overflow the stack
+pub const MAX_DEPTH: usize = 256;
+
+/// Recursive descent parser over the token stream
+///
+/// Precedence from loosest to tightest: `+`, `xor`/`xnor`, `·` (explicit or
+/// by juxtaposition), prefix `!`, postfix `'`. Variables are collected in the
+/// order they first appear and referenced by index.
+pub struct Parser<'a> {
+    tokens: &'a [Token],
+    pos: usize,
+    end: usize,
+    /// Parentheses and `!` currently open
+    depth: usize,
+
+    pub variables: Vec<String>,
+}
+
+impl<'a> Parser<'a> {
+    pub fn new(tokens: &'a [Token], src_len: usize) -> Self {
+        Self {
+            tokens,
+            pos: 0,
+            end: src_len,
+            depth: 0,
+            variables: Vec::new(),
+        }
+    }
+
+    pub fn parse(&mut self) -> Result<Expr, ParseError> {
+        if self.tokens.is_empty() {
+            return Err(ParseError {
+                kind: ParseErrorKind::Empty,
+                span: Span::new(0, self.end),
+            });
+        }
+
+        let expr = self.parse_or()?;
+        match self.peek() {
+            None => Ok(expr),
+            Some(token) if token.kind == TokenKind::RParen => Err(ParseError {
+                kind: ParseErrorKind::UnmatchedParen,
+                span: token.span,
+            }),
+            Some(token) => Err(ParseError {
+                kind: ParseErrorKind::UnexpectedToken,
+                span: token.span,
+            }),
+        }
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next_if(&mut self, matches: impl Fn(&TokenKind) -> bool) -> Option<&Token> {
+        let token = self.tokens.get(self.pos).filter(|t| matches(&t.kind))?;
+        self.pos += 1;
+        Some(token)
+    }
+
+    fn parse_or(&mut self) -> Result<Expr, ParseError> {
+        let mut lhs = self.parse_xor()?;
+        while self.next_if(|k| *k == TokenKind::Or).is_some() {
+            let rhs = self.parse_xor()?;
+            lhs = Expr::Or(Box::new(lhs), Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    fn parse_xor(&mut self) -> Result<Expr, ParseError> {
+        let mut lhs = self.parse_and()?;
+        while let Some(token) = self.next_if(|k| matches!(k, TokenKind::Xor | TokenKind::Xnor)) {
+            let xnor = token.kind == TokenKind::Xnor;
+            let rhs = self.parse_and()?;
+
+            lhs = Expr::Xor(Box::new(lhs), Box::new(rhs));
+            if xnor {
+                lhs = Expr::Not(Box::new(lhs));
+            }
+        }
+        Ok(lhs)
+    }
+
+    fn parse_and(&mut self) -> Result<Expr, ParseError> {
+        let mut lhs = self.parse_unary()?;
+        loop {
+            let explicit = self.next_if(|k| *k == TokenKind::And).is_some();
+            let implicit = self.peek().is_some_and(|t| {
+                matches!(
+                    t.kind,
+                    TokenKind::Ident(_) | TokenKind::Const(_) | TokenKind::Not | TokenKind::LParen
+                )
+            });
+
+            if !explicit && !implicit {
+                return Ok(lhs);
+            }
+
+            let rhs = self.parse_unary()?;
+            lhs = Expr::And(Box::new(lhs), Box::new(rhs));
+        }
+    }
+
+    fn parse_unary(&mut self) -> Result<Expr, ParseError> {
+        if let Some(token) = self.next_if(|k| *k == TokenKind::Not).cloned() {
+            self.descend(&token)?;
+            let inner = self.parse_unary()?;
+            self.depth -= 1;
+            return Ok(Expr::Not(Box::new(inner)));
+        }
+
+        let mut expr = self.parse_atom()?;
+        while self.next_if(|k| *k == TokenKind::Prime).is_some() {
+            expr = Expr::Not(Box::new(expr));
+        }
+        Ok(expr)
+    }
+
+    fn parse_atom(&mut self) -> Result<Expr, ParseError> {
+        let Some(token) = self.tokens.get(self.pos).cloned() else {
+            return Err(ParseError {
+                kind: ParseErrorKind::UnexpectedEnd,
+                span: Span::new(self.end, self.end),
+            });
+        };
+        self.pos += 1;
+
+        match token.kind {
+            TokenKind::Ident(name) => {
+                let index = match self.variables.iter().position(|v| *v == name) {
+                    Some(index) => index,
+                    None => {
+                        self.variables.push(name);
+                        self.variables.len() - 1
+                    }
+                };
+                Ok(Expr::Var(index))
+            }
+
+            TokenKind::Const(value) => Ok(Expr::Const(value)),
+
+            TokenKind::LParen => {
+                self.descend(&token)?;
+                let inner = self.parse_or()?;
+                self.depth -= 1;
+                if self.next_if(|k| *k == TokenKind::RParen).is_none() {
+                    return Err(ParseError {
+                        kind: ParseErrorKind::UnclosedParen,
+                        span: token.span,
+                    });
+                }
+                Ok(inner)
+            }
+
+            TokenKind::RParen => Err(ParseError {
+                kind: ParseErrorKind::UnmatchedParen,
+                span: token.span,
+            }),
+
+            _ => Err(ParseError {
+                kind: ParseErrorKind::UnexpectedToken,
+                span: token.span,
+            }),
+        }
+    }
+
+    /// Enters one more level of nesting at `token`, failing past [`MAX_DEPTH`]
+    fn descend(&mut self, token: &Token) -> Result<(), ParseError> {
+        if self.depth == MAX_DEPTH {
+            return Err(ParseError {
+                kind: ParseErrorKind::TooDeep,
+                span: token.span,
+            });
+        }
+        self.depth += 1;
+        Ok(())
+    }
+}
@@ -1,8 +1,44 @@
+use std::fmt;
+
+/// Largest number of variables karny builds tables and maps for
+///
+/// A table of this size already has 65 536 rows; beyond it memory use and
+/// minimization time stop being reasonable for an interactive tool.
+pub const MAX_VARIABLES: usize = 16;
+
+/// Returned when a function has more variables than [`MAX_VARIABLES`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SizeError {
+    pub variables: usize,
+}
+
+impl fmt::Display for SizeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} variables are not supported, the maximum is {}",
+            self.variables, MAX_VARIABLES
+        )
+    }
+}
+
+impl std::error::Error for SizeError {}
+
+/// Checks that `variables` is within [`MAX_VARIABLES`]
+pub fn check_size(variables: usize) -> Result<(), SizeError> {
+    if variables > MAX_VARIABLES {
+        Err(SizeError { variables })
+    } else {
+        Ok(())
+    }
+}
+
 /// Generate the gray code sequence for n variables
 ///
 /// ### Examples
 /// ```
-/// let seq = gray_sequence(3);
+/// # use karny::logic::gray::gray_sequence;
+/// let seq = gray_sequence(3).unwrap();
 /// assert_eq!(seq, vec![0, 1, 3, 2, 6, 7, 5, 4]);
 /// ```
 ///
@@ -10,15 +46,19 @@
 /// - `n`: The number of bits (variables) in the Gray code.
 ///
 /// ### Returns
-/// A vector containing all `2^n` Gray code values in order.
-pub fn gray_sequence(n: u8) -> Vec<u8> {
-    let size = 1u8 << n;
-    (0..size).map(|i| i ^ (i >> 1)).collect()
+/// A vector containing all `2^n` Gray code values in order, or a
+/// [`SizeError`] if `n` exceeds [`MAX_VARIABLES`].
+pub fn gray_sequence(n: usize) -> Result<Vec<u32>, SizeError> {
+    check_size(n)?;
+
+    let size = 1u32 << n;
+    Ok((0..size).map(binary_to_gray).collect())
 }
 
 /// Return a formatted string representing a gray code
 /// ### Examples
 /// ```
+/// # use karny::logic::gray::format_bits;
 /// assert_eq!(format_bits(1, 2), "01");
 /// assert_eq!(format_bits(5, 4), "0101");
 /// ```
@@ -26,17 +66,32 @@ pub fn gray_sequence(n: u8) -> Vec<u8> {
 /// ### Parameters
 /// - `gray`: The integer value to format.
 /// - `width`: The total number of bits to display.
-pub fn format_bits(gray: u8, width: u8) -> String {
-    format!("{:0width$b}", gray, width = width as usize)
+pub fn format_bits(gray: u32, width: usize) -> String {
+    format!("{:0width$b}", gray, width = width)
 }
 
-pub fn gray_to_binary(mut g: u8) -> u8 {
+pub fn binary_to_gray(b: u32) -> u32 {
+    b ^ (b >> 1)
+}
+
+pub fn gray_to_binary(mut g: u32) -> u32 {
     g ^= g >> 1;
     g ^= g >> 2;
     g ^= g >> 4;
+    g ^= g >> 8;
+    g ^= g >> 16;
     g
 }
 
+/// Mask with the lowest `bits` bits set, saturating at 32 bits
+pub fn low_mask(bits: usize) -> u32 {
+    match bits {
+        0 => 0,
+        b if b >= 32 => u32::MAX,
+        b => (1 << b) - 1,
+    }
+}
+
 /// Computes the K-map coordinates for a given minterm.
 ///
 /// Given a minterm value in binary form and the number of bits assigned to
@@ -53,29 +108,18 @@ pub fn gray_to_binary(mut g: u8) -> u8 {
 ///
 /// ### Examples
 /// ```
+/// # use karny::logic::gray::extract_row_col;
 /// // 5-variable map split as ABC / DE
 /// let (row, col) = extract_row_col(12, 3, 2);
-/// assert_eq!((row, col), (4, 0));
+/// assert_eq!((row, col), (2, 0));
 ///
 /// // 4-variable map split as AB / CD
 /// let (row, col) = extract_row_col(6, 2, 2);
 /// assert_eq!((row, col), (1, 3));
 /// ```
-pub fn extract_row_col(minterm: u8, row_bits: usize, col_bits: usize) -> (u8, u8) {
-    let col_mask: u8 = if col_bits == 0 {
-        0
-    } else {
-        (1u16.wrapping_shl(col_bits as u32) - 1) as u8
-    };
-
-    let row_mask: u8 = if row_bits == 0 {
-        0
-    } else {
-        (1u16.wrapping_shl(row_bits as u32) - 1) as u8
-    };
-
-    let col_gray = minterm & col_mask;
-    let row_gray = (minterm >> col_bits) & row_mask;
+pub fn extract_row_col(minterm: u32, row_bits: usize, col_bits: usize) -> (u32, u32) {
+    let col_gray = minterm & low_mask(col_bits);
+    let row_gray = minterm.checked_shr(col_bits as u32).unwrap_or(0) & low_mask(row_bits);
 
     let row_idx = gray_to_binary(row_gray);
     let col_idx = gray_to_binary(col_gray);
@@ -86,13 +130,13 @@ pub fn extract_row_col(minterm: u8, row_bits: usize, col_bits: usize) -> (u8, u8
 fn can_extract_two_ab() {
     let expected = vec![(0, 0), (0, 1), (1, 0), (1, 1)];
 
-    let result: Vec<(u8, u8)> = (0..4).map(|m| extract_row_col(m, 1, 1)).collect();
+    let result: Vec<(u32, u32)> = (0..4).map(|m| extract_row_col(m, 1, 1)).collect();
     assert_eq!(expected, result);
 }
 
 #[test]
 fn can_extract_three_ab_c() {
-    let expected: Vec<(u8, u8)> = vec![
+    let expected: Vec<(u32, u32)> = vec![
         (0, 0),
         (0, 1),
         (1, 0),
@@ -103,13 +147,13 @@ fn can_extract_three_ab_c() {
         (2, 1),
     ];
 
-    let result: Vec<(u8, u8)> = (0..8).map(|m| extract_row_col(m, 2, 1)).collect();
+    let result: Vec<(u32, u32)> = (0..8).map(|m| extract_row_col(m, 2, 1)).collect();
     assert_eq!(expected, result);
 }
 
 #[test]
 fn can_extract_three_a_bc() {
-    let expected: Vec<(u8, u8)> = vec![
+    let expected: Vec<(u32, u32)> = vec![
         (0, 0),
         (0, 1),
         (0, 3),
@@ -120,13 +164,13 @@ fn can_extract_three_a_bc() {
         (1, 2),
     ];
 
-    let result: Vec<(u8, u8)> = (0..8).map(|m| extract_row_col(m, 1, 2)).collect();
+    let result: Vec<(u32, u32)> = (0..8).map(|m| extract_row_col(m, 1, 2)).collect();
     assert_eq!(expected, result);
 }
 
 #[test]
 fn can_extract_four() {
-    let expected: Vec<(u8, u8)> = vec![
+    let expected: Vec<(u32, u32)> = vec![
         (0, 0),
         (0, 1),
         (0, 3),
@@ -145,7 +189,42 @@ fn can_extract_four() {
         (2, 2),
     ];
 
-    let result: Vec<(u8, u8)> = (0..16).map(|m: u8| extract_row_col(m, 2, 2)).collect();
+    let result: Vec<(u32, u32)> = (0..16).map(|m: u32| extract_row_col(m, 2, 2)).collect();
 
     assert_eq!(expected, result);
 }
+
+#[test]
+fn gray_sequences_up_to_sixteen_bits() {
+    for n in 8..=MAX_VARIABLES {
+        let seq = gray_sequence(n).unwrap();
+        assert_eq!(seq.len(), 1 << n);
+
+        // Neighbours, including the wraparound, differ in exactly one bit
+        for i in 0..seq.len() {
+            let next = seq[(i + 1) % seq.len()];
+            assert_eq!((seq[i] ^ next).count_ones(), 1);
+            assert_eq!(gray_to_binary(seq[i]), i as u32);
+        }
+    }
+}
+
+#[test]
+fn rejects_unsupported_sizes() {
+    assert_eq!(gray_sequence(17), Err(SizeError { variables: 17 }));
+    assert_eq!(
+        SizeError { variables: 20 }.to_string(),
+        "20 variables are not supported, the maximum is 16"
+    );
+    assert!(check_size(MAX_VARIABLES).is_ok());
+}
+
+#[test]
+fn can_extract_sixteen() {
+    // 16-variable map split as 8 / 8
+    assert_eq!(extract_row_col(0xFFFF, 8, 8), (0xAA, 0xAA));
+    assert_eq!(extract_row_col(0x0180, 8, 8), (1, 0xFF));
+    assert_eq!(low_mask(0), 0);
+    assert_eq!(low_mask(8), 0xFF);
+    assert_eq!(low_mask(32), u32::MAX);
+}
@@ -1,15 +1,78 @@
 use crate::logic::{
-    gray::{self, gray_sequence},
+    gray::{self, SizeError},
+    solver::Implicant,
     truth_table::TruthTable,
     variable::BitValue,
 };
 
 use std::fmt;
 
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum KMapError {
+    /// The table has more inputs than [`gray::MAX_VARIABLES`]
+    Size(SizeError),
+    /// The format's row and column variables don't add up to the input count
+    FormatMismatch { format: usize, variables: usize },
+    /// The requested output column doesn't exist
+    NoSuchOutput(usize),
+    /// Stacked sub-maps need 5 or 6 variables
+    NotStackable(usize),
+    /// An axis names a variable that isn't an input of the table
+    UnknownVariable(String),
+    /// A variable is placed on more than one axis, or twice on one
+    DuplicateVariable(String),
+    /// An input of the table is on no axis
+    MissingVariable(String),
+}
+
+impl From<SizeError> for KMapError {
+    fn from(err: SizeError) -> Self {
+        KMapError::Size(err)
+    }
+}
+
+impl fmt::Display for KMapError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KMapError::Size(err) => write!(f, "{err}"),
+            KMapError::FormatMismatch { format, variables } => write!(
+                f,
+                "map layout uses {format} variables but the table has {variables}"
+            ),
+            KMapError::NoSuchOutput(index) => write!(f, "output {index} does not exist"),
+            KMapError::NotStackable(variables) => write!(
+                f,
+                "stacked maps need 5 or 6 variables but the table has {variables}"
+            ),
+            KMapError::UnknownVariable(name) => write!(f, "{name} is not an input"),
+            KMapError::DuplicateVariable(name) => write!(f, "{name} is placed more than once"),
+            KMapError::MissingVariable(name) => write!(f, "{name} is not on any axis"),
+        }
+    }
+}
+
+impl std::error::Error for KMapError {}
+
+/// Side length of one sub-map in a stacked layout
+const SUB_MAP_BITS: usize = 2;
+
+/// Which input goes on which axis of a map, and in what order
+///
+/// The axes may use any subset and order of the inputs. The format remembers
+/// where each axis variable sits in the input list it was built from, so it
+/// only fits tables with those inputs in that order.
 #[derive(Clone, Debug)]
 pub struct KMapFormat {
+    /// Variables choosing one of several sub-maps, empty for a single map
+    ///
+    /// With one variable the sub-maps sit side by side, with two the first
+    /// picks the row of sub-maps and the second the column.
+    pub map_vars: Vec<String>,
     pub row_vars: Vec<String>,
     pub col_vars: Vec<String>,
+
+    /// Input index of each variable in map, row, column order
+    order: Vec<usize>,
 }
 
 impl KMapFormat {
@@ -20,31 +83,437 @@ impl KMapFormat {
         let variables: Vec<String> = variables.iter().cloned().map(Into::into).collect();
 
         let n = variables.len();
-        let r = if n % 2 == 0 { n / 2 } else { n / 2 + 1 };
+        let r = n.div_ceil(2);
 
         Self {
+            map_vars: Vec::new(),
             row_vars: variables[0..r].to_vec(),
             col_vars: variables[r..].to_vec(),
+            order: (0..n).collect(),
         }
     }
 
-    pub fn split<V>(variables: &[V], rows: usize, columns: usize) -> Self
+    pub fn split<V>(variables: &[V], rows: usize, columns: usize) -> Result<Self, KMapError>
     where
         V: Clone + Into<String>,
     {
         let variables: Vec<String> = variables.iter().cloned().map(Into::into).collect();
 
-        assert_eq!(
-            rows + columns,
-            variables.len(),
-            "row_bits + col_bits must equal total number of variables"
-        );
+        if rows + columns != variables.len() {
+            return Err(KMapError::FormatMismatch {
+                format: rows + columns,
+                variables: variables.len(),
+            });
+        }
 
-        Self {
+        Ok(Self {
+            map_vars: Vec::new(),
             row_vars: variables[..rows].to_vec(),
             col_vars: variables[rows..].to_vec(),
+            order: (0..variables.len()).collect(),
+        })
+    }
+
+    /// Lays out 5 or 6 variables as two or four 4×4 sub-maps
+    ///
+    /// The leading variables select the sub-map. Neighbouring sub-maps are
+    /// mirror images of each other, so cells facing each other across the
+    /// edge between them are adjacent.
+    ///
+    /// ### Errors
+    /// Fails with [`KMapError::NotStackable`] for other variable counts.
+    ///
+    /// ### Examples
+    /// ```
+    /// # use karny::logic::kmap::KMapFormat;
+    /// let format = KMapFormat::stacked(&["A", "B", "C", "D", "E"]).unwrap();
+    /// assert_eq!(format.map_vars, vec!["A"]);
+    /// assert_eq!((format.rows(), format.cols()), (4, 8));
+    ///
+    /// // Columns 3 and 4 face each other and differ only in A
+    /// assert_eq!(format.minterm(0, 3) ^ format.minterm(0, 4), 0b10000);
+    /// ```
+    pub fn stacked<V>(variables: &[V]) -> Result<Self, KMapError>
+    where
+        V: Clone + Into<String>,
+    {
+        let variables: Vec<String> = variables.iter().cloned().map(Into::into).collect();
+
+        let n = variables.len();
+        if !(5..=6).contains(&n) {
+            return Err(KMapError::NotStackable(n));
+        }
+
+        let m = n - 2 * SUB_MAP_BITS;
+        Ok(Self {
+            map_vars: variables[..m].to_vec(),
+            row_vars: variables[m..m + SUB_MAP_BITS].to_vec(),
+            col_vars: variables[m + SUB_MAP_BITS..].to_vec(),
+            order: (0..n).collect(),
+        })
+    }
+
+    /// Places each of `inputs` on the axis that names it
+    ///
+    /// ### Parameters
+    /// - `inputs`: The table's inputs, in table order
+    /// - `map_vars`, `row_vars`, `col_vars`: The variables of each axis, most
+    ///   significant gray code bit first
+    ///
+    /// ### Errors
+    /// Every input has to appear on exactly one axis.
+    ///
+    /// ### Examples
+    /// ```
+    /// # use karny::logic::kmap::KMapFormat;
+    /// // A and C on the rows, B and D on the columns
+    /// let format = KMapFormat::new(&["A", "B", "C", "D"], &[], &["A", "C"], &["B", "D"]).unwrap();
+    /// // Row 01 is A=0 C=1, column 10 is B=1 D=0
+    /// assert_eq!(format.minterm(1, 3), 0b0110);
+    /// assert_eq!(format.cell(0b0110), (1, 3));
+    /// ```
+    pub fn new<V>(
+        inputs: &[V],
+        map_vars: &[V],
+        row_vars: &[V],
+        col_vars: &[V],
+    ) -> Result<Self, KMapError>
+    where
+        V: Clone + Into<String>,
+    {
+        let names = |vars: &[V]| -> Vec<String> { vars.iter().cloned().map(Into::into).collect() };
+        let inputs = names(inputs);
+        let (map_vars, row_vars, col_vars) = (names(map_vars), names(row_vars), names(col_vars));
+
+        let mut order = Vec::with_capacity(inputs.len());
+        for name in map_vars.iter().chain(&row_vars).chain(&col_vars) {
+            let index = inputs
+                .iter()
+                .position(|input| input == name)
+                .ok_or_else(|| KMapError::UnknownVariable(name.clone()))?;
+            if order.contains(&index) {
+                return Err(KMapError::DuplicateVariable(name.clone()));
+            }
+            order.push(index);
+        }
+
+        if let Some(missing) = (0..inputs.len()).find(|i| !order.contains(i)) {
+            return Err(KMapError::MissingVariable(inputs[missing].clone()));
+        }
+
+        Ok(Self {
+            map_vars,
+            row_vars,
+            col_vars,
+            order,
+        })
+    }
+
+    /// Moves the input bits of a table row into map, row, column order
+    fn to_layout(&self, bits: u32) -> u32 {
+        let n = self.order.len();
+        self.order.iter().enumerate().fold(0, |out, (k, &input)| {
+            out | (bits >> (n - 1 - input) & 1) << (n - 1 - k)
+        })
+    }
+
+    /// The inverse of [`KMapFormat::to_layout`]
+    fn to_table(&self, bits: u32) -> u32 {
+        let n = self.order.len();
+        self.order.iter().enumerate().fold(0, |out, (k, &input)| {
+            out | (bits >> (n - 1 - k) & 1) << (n - 1 - input)
+        })
+    }
+
+    pub fn variable_count(&self) -> usize {
+        self.map_vars.len() + self.row_vars.len() + self.col_vars.len()
+    }
+
+    pub fn is_stacked(&self) -> bool {
+        !self.map_vars.is_empty()
+    }
+
+    /// Selector bits along the rows and columns of sub-maps
+    fn map_bits(&self) -> (usize, usize) {
+        let rows = self.map_vars.len() / 2;
+        (rows, self.map_vars.len() - rows)
+    }
+
+    /// Number of sub-maps down and across, `(1, 1)` for a single map
+    pub fn map_grid(&self) -> (usize, usize) {
+        let (rbits, cbits) = self.map_bits();
+        (1 << rbits, 1 << cbits)
+    }
+
+    /// Rows and columns of one sub-map
+    pub fn sub_size(&self) -> (usize, usize) {
+        (1 << self.row_vars.len(), 1 << self.col_vars.len())
+    }
+
+    /// Cell rows over all sub-maps
+    pub fn rows(&self) -> usize {
+        self.map_grid().0 * self.sub_size().0
+    }
+
+    /// Cell columns over all sub-maps
+    pub fn cols(&self) -> usize {
+        self.map_grid().1 * self.sub_size().1
+    }
+
+    /// Selector value of the sub-map at `(map_row, map_col)`
+    fn map_value(&self, map_row: usize, map_col: usize) -> u32 {
+        let (_, cbits) = self.map_bits();
+        gray::binary_to_gray(map_row as u32) << cbits | gray::binary_to_gray(map_col as u32)
+    }
+
+    /// Title of the sub-map at `(map_row, map_col)`, such as `AB = 01`
+    pub fn map_label(&self, map_row: usize, map_col: usize) -> String {
+        let bits = self.map_vars.len();
+        format!(
+            "{} = {}",
+            self.map_vars.join(""),
+            gray::format_bits(self.map_value(map_row, map_col), bits)
+        )
+    }
+
+    /// Gray code of the row variables along grid row `row`
+    pub fn row_code(&self, row: usize) -> u32 {
+        gray::binary_to_gray(unmirror(row, self.sub_size().0) as u32)
+    }
+
+    /// Gray code of the column variables along grid column `col`
+    pub fn col_code(&self, col: usize) -> u32 {
+        gray::binary_to_gray(unmirror(col, self.sub_size().1) as u32)
+    }
+
+    /// The minterm shown in grid cell `(row, col)`
+    pub fn minterm(&self, row: usize, col: usize) -> u32 {
+        let (sub_rows, sub_cols) = self.sub_size();
+        let cbits = self.col_vars.len();
+        let bits = self.row_vars.len() + cbits;
+
+        let map = self.map_value(row / sub_rows, col / sub_cols);
+        self.to_table(map << bits | self.row_code(row) << cbits | self.col_code(col))
+    }
+
+    /// The grid cell `(row, col)` showing a minterm, the inverse of [`KMapFormat::minterm`]
+    pub fn cell(&self, minterm: u32) -> (usize, usize) {
+        let (rbits, cbits) = (self.row_vars.len(), self.col_vars.len());
+        let (sub_rows, sub_cols) = self.sub_size();
+
+        let minterm = self.to_layout(minterm);
+        let (row, col) = gray::extract_row_col(minterm, rbits, cbits);
+        let (map_row, map_col) = gray::extract_row_col(
+            minterm >> (rbits + cbits),
+            self.map_bits().0,
+            self.map_bits().1,
+        );
+        let row = map_row as usize * sub_rows + row as usize;
+        let col = map_col as usize * sub_cols + col as usize;
+        (
+            row - row % sub_rows + unmirror(row, sub_rows),
+            col - col % sub_cols + unmirror(col, sub_cols),
+        )
+    }
+
+    /// Computes the rectangles an implicant covers on this map layout
+    ///
+    /// Each axis is scanned in gray code order and split into contiguous
+    /// runs; every pair of a row run and a column run gives one piece. A group
+    /// that wraps around an edge comes back as several pieces, with the edges
+    /// it continues across marked in [`GroupRect::open`]. On mirrored sub-maps
+    /// the runs are flipped to match.
+    ///
+    /// ### Parameters
+    /// - `implicant`: The product term, using the same bit order as the table
+    ///
+    /// ### Examples
+    /// ```
+    /// # use karny::logic::{kmap::KMapFormat, solver::Implicant};
+    /// // B'D' on an AB / CD map covers the four corners
+    /// let format = KMapFormat::auto(&["A", "B", "C", "D"]);
+    /// let rects = format.group_rects(&Implicant { value: 0b0000, mask: 0b1010 });
+    /// assert_eq!(rects.len(), 4);
+    /// ```
+    pub fn group_rects(&self, implicant: &Implicant) -> Vec<GroupRect> {
+        let rbits = self.row_vars.len();
+        let cbits = self.col_vars.len();
+        let implicant = &Implicant {
+            value: self.to_layout(implicant.value),
+            mask: self.to_layout(implicant.mask),
+        };
+
+        let axis = |shift: usize, bits: usize| {
+            let mask = gray::low_mask(bits);
+            let value = implicant.value.checked_shr(shift as u32).unwrap_or(0) & mask;
+            let fixed = !implicant.mask.checked_shr(shift as u32).unwrap_or(0) & mask;
+            axis_runs(value, fixed, bits)
+        };
+
+        let row_runs = axis(cbits, rbits);
+        let col_runs = axis(0, cbits);
+
+        // The group shows up in every sub-map its selector bits allow
+        let shift = (rbits + cbits) as u32;
+        let fixed = !implicant.mask.checked_shr(shift).unwrap_or(0);
+        let value = implicant.value.checked_shr(shift).unwrap_or(0);
+        let (map_rows, map_cols) = self.map_grid();
+        let (sub_rows, sub_cols) = self.sub_size();
+
+        let mut rects = Vec::new();
+        for map_row in 0..map_rows {
+            for map_col in 0..map_cols {
+                let map = self.map_value(map_row, map_col);
+                if map & fixed != value & fixed {
+                    continue;
+                }
+
+                for row in &row_runs {
+                    let (first_row, top, bottom) = row.place(map_row, sub_rows);
+                    for col in &col_runs {
+                        let (first_col, left, right) = col.place(map_col, sub_cols);
+                        rects.push(GroupRect {
+                            row: first_row,
+                            col: first_col,
+                            rows: row.len,
+                            cols: col.len,
+                            open: OpenEdges {
+                                top,
+                                bottom,
+                                left,
+                                right,
+                            },
+                        });
+                    }
+                }
+            }
         }
+        rects
     }
+
+    /// Pairs of pieces from [`KMapFormat::group_rects`] that sit in mirrored
+    /// places on neighbouring sub-maps, and so belong to one group
+    ///
+    /// ### Examples
+    /// ```
+    /// # use karny::logic::{kmap::KMapFormat, solver::Implicant};
+    /// // B'C'D'E' is the outer top corner of both sub-maps
+    /// let format = KMapFormat::stacked(&["A", "B", "C", "D", "E"]).unwrap();
+    /// let rects = format.group_rects(&Implicant { value: 0, mask: 0b10000 });
+    /// assert_eq!((rects[0].col, rects[1].col), (0, 7));
+    /// assert_eq!(format.map_links(&rects), vec![(0, 1)]);
+    /// ```
+    pub fn map_links(&self, rects: &[GroupRect]) -> Vec<(usize, usize)> {
+        let (sub_rows, sub_cols) = self.sub_size();
+        let map_of = |r: &GroupRect| (r.row / sub_rows, r.col / sub_cols);
+        // Where the piece would sit if its sub-map were not mirrored
+        let local = |r: &GroupRect| {
+            let row = unmirror(r.row + r.rows - 1, sub_rows).min(unmirror(r.row, sub_rows));
+            let col = unmirror(r.col + r.cols - 1, sub_cols).min(unmirror(r.col, sub_cols));
+            (row, col, r.rows, r.cols)
+        };
+
+        let mut links = Vec::new();
+        for (i, a) in rects.iter().enumerate() {
+            for (j, b) in rects.iter().enumerate().skip(i + 1) {
+                let (ma, mb) = (map_of(a), map_of(b));
+                let apart = ma.0.abs_diff(mb.0) + ma.1.abs_diff(mb.1);
+                if apart == 1 && local(a) == local(b) {
+                    links.push((i, j));
+                }
+            }
+        }
+        links
+    }
+}
+
+/// Sides of a [`GroupRect`] that continue on the opposite edge of the map
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct OpenEdges {
+    pub top: bool,
+    pub bottom: bool,
+    pub left: bool,
+    pub right: bool,
+}
+
+/// One rectangular piece of a K-map group, in grid cells
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct GroupRect {
+    pub row: usize,
+    pub col: usize,
+    pub rows: usize,
+    pub cols: usize,
+
+    pub open: OpenEdges,
+}
+
+struct Run {
+    start: usize,
+    len: usize,
+    open_start: bool,
+    open_end: bool,
+}
+
+impl Run {
+    /// First grid position and open ends of the run on sub-map `map` along
+    /// an axis of `size` cells per sub-map, flipped if that sub-map is mirrored
+    fn place(&self, map: usize, size: usize) -> (usize, bool, bool) {
+        if map.is_multiple_of(2) {
+            (map * size + self.start, self.open_start, self.open_end)
+        } else {
+            (
+                map * size + size - self.start - self.len,
+                self.open_end,
+                self.open_start,
+            )
+        }
+    }
+}
+
+/// Position in gray code order of grid position `pos` within its sub-map
+///
+/// Every other sub-map along an axis is mirrored, so the axis as a whole
+/// reads as one reflected gray code with the sub-map bits on top. Mirroring
+/// twice gives back the same position, so this also maps back to the grid.
+fn unmirror(pos: usize, size: usize) -> usize {
+    if (pos / size).is_multiple_of(2) {
+        pos % size
+    } else {
+        size - 1 - pos % size
+    }
+}
+
+/// Splits the gray-ordered positions matching `value` on the `fixed` bits
+/// into contiguous runs, flagging the pair of runs joined by wraparound
+fn axis_runs(value: u32, fixed: u32, bits: usize) -> Vec<Run> {
+    let size = 1usize << bits;
+    let mut runs: Vec<Run> = Vec::new();
+
+    for pos in 0..size {
+        if gray::binary_to_gray(pos as u32) & fixed != value & fixed {
+            continue;
+        }
+
+        match runs.last_mut() {
+            Some(run) if run.start + run.len == pos => run.len += 1,
+            _ => runs.push(Run {
+                start: pos,
+                len: 1,
+                open_start: false,
+                open_end: false,
+            }),
+        }
+    }
+
+    let wraps = runs.len() > 1
+        && runs.first().is_some_and(|r| r.start == 0)
+        && runs.last().is_some_and(|r| r.start + r.len == size);
+
+    if wraps {
+        runs.first_mut().unwrap().open_start = true;
+        runs.last_mut().unwrap().open_end = true;
+    }
+    runs
 }
 
 #[derive(Clone, Debug)]
@@ -65,40 +534,81 @@ impl KMap {
     /// - `table`: A reference to the truth table
     /// - `format`: Determines row and column count
     /// - `output_index`: Optional index used for a table with multiple outputs
-    pub fn from_table(table: &TruthTable, format: KMapFormat, output_index: Option<usize>) -> Self {
+    ///
+    /// ### Errors
+    /// Fails if the table is larger than [`gray::MAX_VARIABLES`], if `format`
+    /// wasn't built for the table's inputs, or if the output doesn't exist.
+    pub fn from_table(
+        table: &TruthTable,
+        format: KMapFormat,
+        output_index: Option<usize>,
+    ) -> Result<Self, KMapError> {
         let variables = table.inputs.clone();
+        gray::check_size(variables.len())?;
+
+        if format.variable_count() != variables.len() {
+            return Err(KMapError::FormatMismatch {
+                format: format.variable_count(),
+                variables: variables.len(),
+            });
+        }
+        let placed = format
+            .map_vars
+            .iter()
+            .chain(&format.row_vars)
+            .chain(&format.col_vars);
+        if let Some((name, _)) = placed
+            .zip(&format.order)
+            .find(|&(name, &index)| *name != variables[index])
+        {
+            return Err(KMapError::UnknownVariable(name.clone()));
+        }
+
+        let output_index = output_index.unwrap_or(0);
+        if output_index >= table.outputs.len() {
+            return Err(KMapError::NoSuchOutput(output_index));
+        }
 
-        let row_count = 1 << format.row_vars.len();
-        let col_count = 1 << format.col_vars.len();
+        let row_count = format.rows();
+        let col_count = format.cols();
 
         let mut grid = vec![vec![BitValue::Zero; col_count]; row_count];
-        for minterm in 0..1u8 << variables.len() {
-            let value = &table.rows[minterm as usize].outputs[output_index.unwrap_or(0)];
-            let (r, c) =
-                gray::extract_row_col(minterm, format.row_vars.len(), format.col_vars.len());
-
-            grid[r as usize][c as usize] = value.clone();
+        for (minterm, row) in table.rows.iter().enumerate() {
+            let (r, c) = format.cell(minterm as u32);
+            grid[r][c] = row.outputs[output_index];
         }
 
-        Self {
+        Ok(Self {
             rows: row_count,
             cols: col_count,
 
             format,
             variables,
 
-            grid: grid,
-        }
+            grid,
+        })
+    }
+
+    /// The minterm (truth table row) shown in grid cell `(row, col)`
+    pub fn minterm(&self, row: usize, col: usize) -> u32 {
+        self.format.minterm(row, col)
+    }
+
+    /// The grid cell `(row, col)` showing a minterm, the inverse of [`KMap::minterm`]
+    pub fn cell(&self, minterm: u32) -> (usize, usize) {
+        self.format.cell(minterm)
     }
 }
 
-impl fmt::Display for KMap {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+impl KMap {
+    /// Writes the sub-map whose top-left cell is `(row0, col0)` as a text grid
+    fn fmt_grid(&self, f: &mut fmt::Formatter<'_>, row0: usize, col0: usize) -> fmt::Result {
+        let (sub_rows, sub_cols) = self.format.sub_size();
         let row_vars = self.format.row_vars.join("");
         let col_vars = self.format.col_vars.join("");
 
-        let rbits = row_vars.len();
-        let cbits = col_vars.len();
+        let rbits = self.format.row_vars.len();
+        let cbits = self.format.col_vars.len();
 
         let col_inner_w = cbits.max(1);
         let row_pad = rbits.max(1) + 1;
@@ -107,9 +617,8 @@ impl fmt::Display for KMap {
         let cell = |s: &str| format!(" {:^w$} ", s, w = col_inner_w);
         let dash_cell = || "-".repeat(col_inner_w + 2);
 
-        let col_hdr_cells = gray_sequence(cbits as u8)
-            .into_iter()
-            .map(|v| gray::format_bits(v, cbits as u8))
+        let col_hdr_cells = (col0..col0 + sub_cols)
+            .map(|c| gray::format_bits(self.format.col_code(c), cbits))
             .map(|s| cell(&s))
             .collect::<Vec<_>>();
 
@@ -121,9 +630,8 @@ impl fmt::Display for KMap {
         let header_line = col_hdr_cells.join("|");
 
         // Row gray labels
-        let row_labels = gray_sequence(rbits as u8)
-            .into_iter()
-            .map(|v| gray::format_bits(v, rbits as u8))
+        let row_labels = (row0..row0 + sub_rows)
+            .map(|r| gray::format_bits(self.format.row_code(r), rbits))
             .collect::<Vec<_>>();
 
         // Top header
@@ -148,8 +656,8 @@ impl fmt::Display for KMap {
         )?;
 
         // Rows
-        for (i, row) in self.grid.iter().enumerate() {
-            let values = row
+        for (i, row) in self.grid[row0..row0 + sub_rows].iter().enumerate() {
+            let values = row[col0..col0 + sub_cols]
                 .iter()
                 .map(|v| cell(&v.to_char().to_string()))
                 .collect::<Vec<_>>()
@@ -175,3 +683,335 @@ impl fmt::Display for KMap {
         Ok(())
     }
 }
+
+impl fmt::Display for KMap {
+    /// Stacked layouts print one titled grid per sub-map, top to bottom
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if !self.format.is_stacked() {
+            return self.fmt_grid(f, 0, 0);
+        }
+
+        let (map_rows, map_cols) = self.format.map_grid();
+        let (sub_rows, sub_cols) = self.format.sub_size();
+        for map_row in 0..map_rows {
+            for map_col in 0..map_cols {
+                if map_row + map_col > 0 {
+                    writeln!(f)?;
+                }
+                writeln!(f, "{}", self.format.map_label(map_row, map_col))?;
+                self.fmt_grid(f, map_row * sub_rows, map_col * sub_cols)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn maps_eight_to_sixteen_variables() {
+    for n in 8..=gray::MAX_VARIABLES {
+        let names: Vec<String> = (0..n).map(|i| format!("x{i}")).collect();
+        let mut table = TruthTable::new(&names, &["F"]);
+        for row in 0..table.rows.len() {
+            table.set(row, 0, BitValue::from_u8((row.count_ones() % 2) as u8));
+        }
+
+        let format = KMapFormat::auto(&names);
+        let (rbits, cbits) = (format.row_vars.len(), format.col_vars.len());
+        let kmap = KMap::from_table(&table, format, None).unwrap();
+        assert_eq!(kmap.rows * kmap.cols, 1 << n);
+
+        for (r, row) in kmap.grid.iter().enumerate() {
+            for (c, value) in row.iter().enumerate() {
+                let minterm = kmap.minterm(r, c);
+                assert_eq!(
+                    gray::extract_row_col(minterm, rbits, cbits),
+                    (r as u32, c as u32)
+                );
+                assert_eq!(kmap.cell(minterm), (r, c));
+                assert!(minterm >> (rbits + cbits) == 0);
+                assert_eq!(*value, BitValue::from_u8((minterm.count_ones() % 2) as u8));
+            }
+        }
+    }
+}
+
+#[test]
+fn reports_unsupported_layouts() {
+    let table = TruthTable::new(&["A", "B", "C"], &["F"]);
+
+    assert_eq!(
+        KMapFormat::split(&table.inputs, 2, 2).unwrap_err(),
+        KMapError::FormatMismatch {
+            format: 4,
+            variables: 3
+        }
+    );
+
+    let format = KMapFormat::split(&["A", "B"], 1, 1).unwrap();
+    assert!(matches!(
+        KMap::from_table(&table, format, None),
+        Err(KMapError::FormatMismatch { .. })
+    ));
+
+    let format = KMapFormat::auto(&table.inputs);
+    assert_eq!(
+        KMap::from_table(&table, format, Some(1)).unwrap_err(),
+        KMapError::NoSuchOutput(1)
+    );
+
+    let names: Vec<String> = (0..17).map(|i| format!("x{i}")).collect();
+    assert_eq!(
+        TruthTable::try_new(&names, &["F"]).unwrap_err(),
+        SizeError { variables: 17 }
+    );
+}
+
+#[cfg(test)]
+fn rect(row: usize, col: usize, rows: usize, cols: usize, open: OpenEdges) -> GroupRect {
+    GroupRect {
+        row,
+        col,
+        rows,
+        cols,
+        open,
+    }
+}
+
+#[test]
+fn splits_corner_groups() {
+    // B'D' on AB / CD: rows 00 and 10, columns 00 and 10
+    let format = KMapFormat::auto(&["A", "B", "C", "D"]);
+    let rects = format.group_rects(&Implicant {
+        value: 0b0000,
+        mask: 0b1010,
+    });
+
+    let open = |top, bottom, left, right| OpenEdges {
+        top,
+        bottom,
+        left,
+        right,
+    };
+    assert_eq!(
+        rects,
+        vec![
+            rect(0, 0, 1, 1, open(true, false, true, false)),
+            rect(0, 3, 1, 1, open(true, false, false, true)),
+            rect(3, 0, 1, 1, open(false, true, true, false)),
+            rect(3, 3, 1, 1, open(false, true, false, true)),
+        ]
+    );
+}
+
+#[test]
+fn keeps_contiguous_groups_whole() {
+    let format = KMapFormat::auto(&["A", "B", "C", "D"]);
+
+    // A'B' is the whole first row
+    let rects = format.group_rects(&Implicant {
+        value: 0b0000,
+        mask: 0b0011,
+    });
+    assert_eq!(rects, vec![rect(0, 0, 1, 4, OpenEdges::default())]);
+
+    // BD covers the middle two rows and columns
+    let rects = format.group_rects(&Implicant {
+        value: 0b0101,
+        mask: 0b1010,
+    });
+    assert_eq!(rects, vec![rect(1, 1, 2, 2, OpenEdges::default())]);
+}
+
+#[test]
+fn handles_uneven_splits() {
+    // C on an ABC / DE map sits in rows 001, 011, 111, 101: two separate
+    // pieces that are adjacent only by reflection, not by wraparound
+    let format = KMapFormat::split(&["A", "B", "C", "D", "E"], 3, 2).unwrap();
+    let rects = format.group_rects(&Implicant {
+        value: 0b00100,
+        mask: 0b11011,
+    });
+    assert_eq!(
+        rects,
+        vec![
+            rect(1, 0, 2, 4, OpenEdges::default()),
+            rect(5, 0, 2, 4, OpenEdges::default()),
+        ]
+    );
+
+    // A single row of variables still works
+    let format = KMapFormat::split(&["A", "B"], 0, 2).unwrap();
+    let rects = format.group_rects(&Implicant {
+        value: 0b00,
+        mask: 0b10,
+    });
+    assert_eq!(rects.len(), 2);
+    assert!(rects[0].open.left && rects[1].open.right);
+}
+
+#[test]
+fn stacks_five_and_six_variables() {
+    for n in 5..=6 {
+        let names: Vec<String> = (0..n).map(|i| format!("x{i}")).collect();
+        let mut table = TruthTable::new(&names, &["F"]);
+        for row in 0..table.rows.len() {
+            table.set(row, 0, BitValue::from_u8((row.count_ones() % 2) as u8));
+        }
+
+        let format = KMapFormat::stacked(&names).unwrap();
+        assert_eq!(format.sub_size(), (4, 4));
+        let kmap = KMap::from_table(&table, format, None).unwrap();
+        assert_eq!(kmap.rows * kmap.cols, 1 << n);
+
+        for (r, row) in kmap.grid.iter().enumerate() {
+            for (c, value) in row.iter().enumerate() {
+                let minterm = kmap.minterm(r, c);
+                assert_eq!(kmap.cell(minterm), (r, c));
+                assert_eq!(*value, BitValue::from_u8((minterm.count_ones() % 2) as u8));
+            }
+        }
+    }
+
+    // A picks the row of sub-maps and B the column, and the sub-maps mirror
+    // each other so cells facing across an edge differ only there
+    let format = KMapFormat::stacked(&["A", "B", "C", "D", "E", "F"]).unwrap();
+    assert_eq!((format.rows(), format.cols()), (8, 8));
+    assert_eq!(format.minterm(4, 0), 0b101000);
+    assert_eq!(format.minterm(3, 0) ^ format.minterm(4, 0), 0b100000);
+    assert_eq!(format.minterm(0, 3) ^ format.minterm(0, 4), 0b010000);
+    assert_eq!(format.map_label(1, 0), "AB = 10");
+    assert_eq!(
+        (0..8).map(|c| format.col_code(c)).collect::<Vec<_>>(),
+        vec![0b00, 0b01, 0b11, 0b10, 0b10, 0b11, 0b01, 0b00]
+    );
+
+    // C'D'E'F' is the outer corner of all four sub-maps, linked in a square
+    let rects = format.group_rects(&Implicant {
+        value: 0,
+        mask: 0b110000,
+    });
+    assert_eq!(
+        rects.iter().map(|r| (r.row, r.col)).collect::<Vec<_>>(),
+        vec![(0, 0), (0, 7), (7, 0), (7, 7)]
+    );
+    assert_eq!(
+        format.map_links(&rects),
+        vec![(0, 1), (0, 2), (1, 3), (2, 3)]
+    );
+
+    // With A fixed the group stays on the bottom two sub-maps
+    let rects = format.group_rects(&Implicant {
+        value: 0b100000,
+        mask: 0b010000,
+    });
+    assert_eq!(
+        rects.iter().map(|r| (r.row, r.col)).collect::<Vec<_>>(),
+        vec![(7, 0), (7, 7)]
+    );
+
+    // C'F' wraps around the columns, the pieces swap sides on mirrored maps
+    let rects = format.group_rects(&Implicant {
+        value: 0,
+        mask: 0b110110,
+    });
+    assert_eq!(
+        rects
+            .iter()
+            .map(|r| (r.row, r.col, r.open.left, r.open.right))
+            .collect::<Vec<_>>()[..4],
+        [
+            (0, 0, true, false),
+            (0, 3, false, true),
+            (0, 7, false, true),
+            (0, 4, true, false)
+        ]
+    );
+    assert_eq!(rects[4].row, 6);
+    assert!(
+        format
+            .map_links(&rects)
+            .iter()
+            .all(|&(a, b)| rects[a].rows == rects[b].rows && rects[a].cols == rects[b].cols)
+    );
+
+    assert_eq!(
+        KMapFormat::stacked(&["A", "B", "C", "D"]).unwrap_err(),
+        KMapError::NotStackable(4)
+    );
+}
+
+#[test]
+fn prints_one_grid_per_sub_map() {
+    let table = TruthTable::new(&["A", "B", "C", "D", "E"], &["F"]);
+    let format = KMapFormat::stacked(&table.inputs).unwrap();
+    let text = KMap::from_table(&table, format, None).unwrap().to_string();
+
+    // The first sub-map prints like the 4-variable map of the remaining
+    // inputs, the second one with its columns mirrored
+    let rest = TruthTable::new(&["B", "C", "D", "E"], &["F"]);
+    let grid = KMap::from_table(&rest, KMapFormat::auto(&rest.inputs), None)
+        .unwrap()
+        .to_string();
+    let mirrored = grid.replacen("00 | 01 | 11 | 10", "10 | 11 | 01 | 00", 1);
+    assert_eq!(text, format!("A = 0\n{grid}\nA = 1\n{mirrored}"));
+}
+
+#[test]
+fn maps_any_axis_assignment() {
+    let inputs = ["A", "B", "C", "D", "E"];
+    let mut table = TruthTable::new(&inputs, &["F"]);
+    for row in 0..table.rows.len() {
+        table.set(row, 0, BitValue::from_u8((row % 3 == 0) as u8));
+    }
+
+    let layouts: [(&[&str], &[&str], &[&str]); 4] = [
+        (&[], &["C", "A"], &["E", "B", "D"]),
+        (&[], &[], &["B", "D", "A", "C", "E"]),
+        (&["E"], &["A", "B"], &["D", "C"]),
+        (&["D", "A"], &["C"], &["B", "E"]),
+    ];
+    for (maps, rows, cols) in layouts {
+        let format = KMapFormat::new(&inputs, maps, rows, cols).unwrap();
+        let kmap = KMap::from_table(&table, format, None).unwrap();
+        for (r, row) in kmap.grid.iter().enumerate() {
+            for (c, value) in row.iter().enumerate() {
+                let minterm = kmap.minterm(r, c);
+                assert_eq!(kmap.cell(minterm), (r, c));
+                assert_eq!(*value, BitValue::from_u8(minterm.is_multiple_of(3) as u8));
+            }
+        }
+    }
+
+    // With C, A on the rows, A is the low row bit: rows 01 and 11 in the middle
+    let format = KMapFormat::new(&inputs, &[], &["C", "A"], &["E", "B", "D"]).unwrap();
+    let rects = format.group_rects(&Implicant {
+        value: 0b10000,
+        mask: 0b01111,
+    });
+    assert_eq!(rects, vec![rect(1, 0, 2, 8, OpenEdges::default())]);
+}
+
+#[test]
+fn rejects_bad_axis_assignments() {
+    let inputs = ["A", "B", "C"];
+    assert_eq!(
+        KMapFormat::new(&inputs, &[], &["A", "X"], &["B", "C"]).unwrap_err(),
+        KMapError::UnknownVariable("X".to_string())
+    );
+    assert_eq!(
+        KMapFormat::new(&inputs, &[], &["A", "B"], &["B", "C"]).unwrap_err(),
+        KMapError::DuplicateVariable("B".to_string())
+    );
+    assert_eq!(
+        KMapFormat::new(&inputs, &[], &["A"], &["C"]).unwrap_err(),
+        KMapError::MissingVariable("B".to_string())
+    );
+
+    // A format only fits the inputs it was built for
+    let table = TruthTable::new(&["A", "C", "B"], &["F"]);
+    let format = KMapFormat::new(&inputs, &[], &["A"], &["B", "C"]).unwrap();
+    assert_eq!(
+        KMap::from_table(&table, format, None).unwrap_err(),
+        KMapError::UnknownVariable("B".to_string())
+    );
+}
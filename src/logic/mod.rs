@@ -1,4 +1,9 @@
+pub mod canonical;
+pub mod expression;
 pub mod gray;
 pub mod kmap;
+pub mod notation;
+pub mod practice;
+pub mod solver;
 pub mod truth_table;
 pub mod variable;
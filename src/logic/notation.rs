@@ -0,0 +1,288 @@
+use std::fmt;
+
+use crate::logic::solver::{Form, Implicant, Solution};
+
+/// Output syntax for minimized expressions
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Notation {
+    /// `A'B + C`
+    #[default]
+    Prime,
+    /// `!A && B || C`
+    C,
+    /// `¬A∧B ∨ C`
+    Unicode,
+    /// `\overline{A}B + C`
+    Latex,
+    /// `~A & B | C`
+    Verilog,
+    /// `not A and B or C`
+    Python,
+}
+
+impl Notation {
+    pub const ALL: [Notation; 6] = [
+        Notation::Prime,
+        Notation::C,
+        Notation::Unicode,
+        Notation::Latex,
+        Notation::Verilog,
+        Notation::Python,
+    ];
+
+    fn symbols(self) -> Symbols {
+        match self {
+            Notation::Prime => Symbols {
+                not: ("", "'"),
+                and: "",
+                or: " + ",
+                constants: ("0", "1"),
+            },
+            Notation::C => Symbols {
+                not: ("!", ""),
+                and: " && ",
+                or: " || ",
+                constants: ("0", "1"),
+            },
+            Notation::Unicode => Symbols {
+                not: ("¬", ""),
+                and: "∧",
+                or: " ∨ ",
+                constants: ("0", "1"),
+            },
+            Notation::Latex => Symbols {
+                not: ("\\overline{", "}"),
+                and: "",
+                or: " + ",
+                constants: ("0", "1"),
+            },
+            Notation::Verilog => Symbols {
+                not: ("~", ""),
+                and: " & ",
+                or: " | ",
+                constants: ("1'b0", "1'b1"),
+            },
+            Notation::Python => Symbols {
+                not: ("not ", ""),
+                and: " and ",
+                or: " or ",
+                constants: ("False", "True"),
+            },
+        }
+    }
+}
+
+impl fmt::Display for Notation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Notation::Prime => "Prime (A'B)",
+                Notation::C => "C (!A && B)",
+                Notation::Unicode => "Unicode (¬A∧B)",
+                Notation::Latex => "LaTeX (\\overline{A}B)",
+                Notation::Verilog => "Verilog (~A & B)",
+                Notation::Python => "Python (not A and B)",
+            }
+        )
+    }
+}
+
+struct Symbols {
+    /// Text placed before and after a complemented variable
+    not: (&'static str, &'static str),
+    and: &'static str,
+    or: &'static str,
+    /// Spelling of false and true
+    constants: (&'static str, &'static str),
+}
+
+impl Symbols {
+    /// Juxtaposition only reads unambiguously when every name is a single
+    /// letter, optionally followed by digits
+    fn and_for(&self, names: &[String]) -> &'static str {
+        let simple = names.iter().all(|n| {
+            let mut chars = n.chars();
+            chars.next().is_some_and(char::is_alphabetic) && chars.all(|c| c.is_ascii_digit())
+        });
+
+        if self.and.is_empty() && !simple {
+            "·"
+        } else {
+            self.and
+        }
+    }
+
+    fn literal(&self, name: &str, positive: bool) -> String {
+        if positive {
+            name.to_string()
+        } else {
+            format!("{}{name}{}", self.not.0, self.not.1)
+        }
+    }
+}
+
+/// Renders a list of implicants as an SOP or POS expression
+///
+/// For [`Form::Pos`] each implicant is a group of zeros and becomes one sum
+/// term with its literals complemented.
+///
+/// ### Parameters
+/// - `terms`: The implicants of the cover
+/// - `form`: How to read the implicants
+/// - `names`: Variable names, usually `TruthTable::inputs`
+/// - `notation`: Output syntax
+///
+/// ### Examples
+/// ```
+/// # use karny::logic::{notation::*, solver::{Form, Implicant}};
+/// let terms = [Implicant { value: 0b01, mask: 0 }];
+/// let names = ["A".to_string(), "B".to_string()];
+/// assert_eq!(format_terms(&terms, Form::Sop, &names, Notation::Prime), "A'B");
+/// assert_eq!(format_terms(&terms, Form::Pos, &names, Notation::C), "(A || !B)");
+/// ```
+pub fn format_terms(
+    terms: &[Implicant],
+    form: Form,
+    names: &[String],
+    notation: Notation,
+) -> String {
+    let symbols = notation.symbols();
+    let (zero, one) = symbols.constants;
+    let and = symbols.and_for(names);
+
+    match form {
+        Form::Sop => {
+            if terms.is_empty() {
+                return zero.to_string();
+            }
+
+            terms
+                .iter()
+                .map(|term| {
+                    let literals = term.literals(names.len());
+                    if literals.is_empty() {
+                        return one.to_string();
+                    }
+
+                    literals
+                        .into_iter()
+                        .map(|(i, positive)| symbols.literal(&names[i], positive))
+                        .collect::<Vec<_>>()
+                        .join(and)
+                })
+                .collect::<Vec<_>>()
+                .join(symbols.or)
+        }
+
+        Form::Pos => {
+            if terms.is_empty() {
+                return one.to_string();
+            }
+
+            terms
+                .iter()
+                .map(|term| {
+                    let literals = term.literals(names.len());
+                    if literals.is_empty() {
+                        return zero.to_string();
+                    }
+
+                    let sum = literals
+                        .into_iter()
+                        .map(|(i, value)| symbols.literal(&names[i], !value))
+                        .collect::<Vec<_>>()
+                        .join(symbols.or);
+                    format!("({sum})")
+                })
+                .collect::<Vec<_>>()
+                .join(and)
+        }
+    }
+}
+
+/// Renders the selected cover of a solution
+pub fn format_solution(solution: &Solution, names: &[String], notation: Notation) -> String {
+    format_terms(&solution.cover, solution.form, names, notation)
+}
+
+#[cfg(test)]
+fn names(names: &[&str]) -> Vec<String> {
+    names.iter().map(|n| n.to_string()).collect()
+}
+
+#[test]
+fn renders_every_notation() {
+    // A'B + C
+    let terms = [
+        Implicant {
+            value: 0b010,
+            mask: 0b001,
+        },
+        Implicant {
+            value: 0b001,
+            mask: 0b110,
+        },
+    ];
+    let abc = names(&["A", "B", "C"]);
+    let render = |n| format_terms(&terms, Form::Sop, &abc, n);
+
+    assert_eq!(render(Notation::Prime), "A'B + C");
+    assert_eq!(render(Notation::C), "!A && B || C");
+    assert_eq!(render(Notation::Unicode), "¬A∧B ∨ C");
+    assert_eq!(render(Notation::Latex), "\\overline{A}B + C");
+    assert_eq!(render(Notation::Verilog), "~A & B | C");
+    assert_eq!(render(Notation::Python), "not A and B or C");
+}
+
+#[test]
+fn renders_product_of_sums() {
+    // Zeros at A'C' and B'C' give (A + C)(B + C)
+    let terms = [
+        Implicant {
+            value: 0b000,
+            mask: 0b010,
+        },
+        Implicant {
+            value: 0b000,
+            mask: 0b100,
+        },
+    ];
+    let abc = names(&["A", "B", "C"]);
+
+    assert_eq!(
+        format_terms(&terms, Form::Pos, &abc, Notation::Prime),
+        "(A + C)(B + C)"
+    );
+    assert_eq!(
+        format_terms(&terms, Form::Pos, &abc, Notation::Python),
+        "(A or C) and (B or C)"
+    );
+}
+
+#[test]
+fn renders_constants_and_long_names() {
+    let ab = names(&["A", "B"]);
+    assert_eq!(format_terms(&[], Form::Sop, &ab, Notation::Verilog), "1'b0");
+    assert_eq!(format_terms(&[], Form::Pos, &ab, Notation::Python), "True");
+
+    let everything = [Implicant {
+        value: 0,
+        mask: 0b11,
+    }];
+    assert_eq!(
+        format_terms(&everything, Form::Sop, &ab, Notation::Prime),
+        "1"
+    );
+
+    let carry = names(&["Cin", "A"]);
+    let term = [Implicant {
+        value: 0b10,
+        mask: 0,
+    }];
+    assert_eq!(
+        format_terms(&term, Form::Sop, &carry, Notation::Prime),
+        "Cin·A'"
+    );
+}
@@ -0,0 +1,363 @@
+use crate::logic::{
+    notation::{self, Notation},
+    solver::{Cost, Form, Implicant, Solution},
+};
+
+/// Something wrong with a group drawn by hand
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum GroupIssue {
+    Empty,
+    /// Groups hold 1, 2, 4, 8, ... cells
+    NotPowerOfTwo(usize),
+    /// The cells don't form one block, even allowing for wraparound
+    NotAdjacent,
+    /// Cells outside the function, 0s for SOP and 1s for POS
+    CoversOff(Vec<u32>),
+    /// The group fits inside a larger valid one
+    NotPrime(Implicant),
+}
+
+impl GroupIssue {
+    /// Explains the issue, writing terms with `names` in `notation`
+    pub fn describe(&self, form: Form, names: &[String], notation: Notation) -> String {
+        let off = match form {
+            Form::Sop => "0",
+            Form::Pos => "1",
+        };
+        match self {
+            GroupIssue::Empty => "the group has no cells".to_string(),
+            GroupIssue::NotPowerOfTwo(size) => {
+                format!("{size} cells is not a power of two")
+            }
+            GroupIssue::NotAdjacent => "the cells are not one adjacent block".to_string(),
+            GroupIssue::CoversOff(cells) => format!("covers the {off} cells {}", list(cells)),
+            GroupIssue::NotPrime(larger) => format!(
+                "not prime, it can grow to {}",
+                notation::format_terms(&[*larger], form, names, notation)
+            ),
+        }
+    }
+}
+
+/// Result of checking one hand-drawn group
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GroupCheck {
+    /// Sorted minterms of the group
+    pub cells: Vec<u32>,
+    /// The term the group stands for, when its cells form a block
+    pub implicant: Option<Implicant>,
+    pub issues: Vec<GroupIssue>,
+}
+
+impl GroupCheck {
+    /// A block of 1s and don't cares, prime or not
+    pub fn is_valid(&self) -> bool {
+        self.implicant.is_some()
+            && !self
+                .issues
+                .iter()
+                .any(|i| !matches!(i, GroupIssue::NotPrime(_)))
+    }
+}
+
+/// Checks a group of cells against the function behind `solution`
+///
+/// Works on minterm numbers, so a block that wraps around the map edge or
+/// mirrors across a 3-bit axis is as valid as a contiguous one.
+///
+/// ### Examples
+/// ```
+/// # use karny::logic::{practice::{GroupIssue, check_group}, solver::{Form, Solution}};
+/// let solution = Solution { form: Form::Sop, variables: 2, minterms: vec![1, 3], ..Default::default() };
+/// assert!(check_group(&[1, 3], &solution).issues.is_empty());
+/// assert_eq!(check_group(&[1], &solution).issues.len(), 1);
+/// ```
+pub fn check_group(cells: &[u32], solution: &Solution) -> GroupCheck {
+    let mut cells = cells.to_vec();
+    cells.sort_unstable();
+    cells.dedup();
+
+    let mut issues = Vec::new();
+    if cells.is_empty() {
+        issues.push(GroupIssue::Empty);
+    } else if !cells.len().is_power_of_two() {
+        issues.push(GroupIssue::NotPowerOfTwo(cells.len()));
+    }
+
+    let implicant = block(&cells);
+    if implicant.is_none() && !cells.is_empty() && cells.len().is_power_of_two() {
+        issues.push(GroupIssue::NotAdjacent);
+    }
+
+    let allowed = |m: &u32| solution.minterms.contains(m) || solution.dont_cares.contains(m);
+    let off: Vec<u32> = cells.iter().copied().filter(|m| !allowed(m)).collect();
+    if !off.is_empty() {
+        issues.push(GroupIssue::CoversOff(off));
+    }
+
+    if let Some(term) = implicant.filter(|_| issues.is_empty()) {
+        let prime = expand(term, solution);
+        if prime != term {
+            issues.push(GroupIssue::NotPrime(prime));
+        }
+    }
+
+    GroupCheck {
+        cells,
+        implicant,
+        issues,
+    }
+}
+
+/// The implicant whose minterms are exactly `cells`, if there is one
+fn block(cells: &[u32]) -> Option<Implicant> {
+    let first = *cells.first()?;
+    let and = cells.iter().fold(first, |acc, m| acc & m);
+    let or = cells.iter().fold(first, |acc, m| acc | m);
+
+    let term = Implicant {
+        value: and,
+        mask: and ^ or,
+    };
+    (term.minterms() == cells).then_some(term)
+}
+
+/// Grows `term` one variable at a time while it stays inside the function
+fn expand(mut term: Implicant, solution: &Solution) -> Implicant {
+    let allowed = |m: u32| solution.minterms.contains(&m) || solution.dont_cares.contains(&m);
+
+    for i in 0..solution.variables {
+        let bit = 1 << i;
+        if term.mask & bit != 0 {
+            continue;
+        }
+
+        let larger = Implicant {
+            value: term.value & !bit,
+            mask: term.mask | bit,
+        };
+        if larger.minterms().into_iter().all(allowed) {
+            term = larger;
+        }
+    }
+    term
+}
+
+/// Comparison of a hand-drawn cover with the solver's minimal one
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Review {
+    pub form: Form,
+    pub groups: Vec<GroupCheck>,
+    /// Cells of the function that no valid group covers
+    pub uncovered: Vec<u32>,
+    /// Indices of valid groups whose cells are all covered by other groups
+    pub redundant: Vec<usize>,
+    /// Cost of the valid groups
+    pub cost: Cost,
+    pub minimal_cost: Cost,
+    /// Terms of the minimal cover that no group matches
+    pub unused: Vec<Implicant>,
+}
+
+impl Review {
+    /// Every cell covered by prime, non-redundant groups at minimal cost
+    pub fn is_minimal(&self) -> bool {
+        self.uncovered.is_empty()
+            && self.redundant.is_empty()
+            && self.groups.iter().all(|g| g.issues.is_empty())
+            && self.cost == self.minimal_cost
+    }
+
+    /// One line per finding, first the overall verdict, with terms written in
+    /// `notation`
+    pub fn messages(&self, names: &[String], notation: Notation) -> Vec<String> {
+        let term = |t: &Implicant| notation::format_terms(&[*t], self.form, names, notation);
+        let mut out = Vec::new();
+
+        if self.is_minimal() {
+            out.push("Your cover is minimal".to_string());
+            return out;
+        }
+
+        for (i, group) in self.groups.iter().enumerate() {
+            for issue in &group.issues {
+                out.push(format!(
+                    "Group {}: {}",
+                    i + 1,
+                    issue.describe(self.form, names, notation)
+                ));
+            }
+        }
+        if !self.uncovered.is_empty() {
+            out.push(format!("Cells {} are not covered", list(&self.uncovered)));
+        }
+        for &i in &self.redundant {
+            out.push(format!(
+                "Group {} is redundant, the other groups already cover it",
+                i + 1
+            ));
+        }
+        if self.cost > self.minimal_cost {
+            out.push(format!(
+                "Your cover uses {} terms and {} literals, the minimum is {} and {}",
+                self.cost.terms,
+                self.cost.literals,
+                self.minimal_cost.terms,
+                self.minimal_cost.literals
+            ));
+        }
+        if !self.unused.is_empty() {
+            let terms: Vec<String> = self.unused.iter().map(term).collect();
+            out.push(format!("A minimal cover uses {}", terms.join(", ")));
+        }
+        out
+    }
+}
+
+/// Checks every group and compares the valid ones with `solution.cover`
+pub fn review(groups: &[Vec<u32>], solution: &Solution) -> Review {
+    let groups: Vec<GroupCheck> = groups.iter().map(|g| check_group(g, solution)).collect();
+    let valid: Vec<(usize, Implicant)> = groups
+        .iter()
+        .enumerate()
+        .filter(|(_, g)| g.is_valid())
+        .filter_map(|(i, g)| Some((i, g.implicant?)))
+        .collect();
+
+    let uncovered = solution
+        .minterms
+        .iter()
+        .copied()
+        .filter(|&m| !valid.iter().any(|(_, t)| t.covers(m)))
+        .collect();
+
+    // Dropped one at a time, so of two identical groups only one is flagged
+    let mut redundant = Vec::new();
+    for &(i, term) in &valid {
+        let others_cover = term
+            .minterms()
+            .into_iter()
+            .filter(|m| solution.minterms.contains(m))
+            .all(|m| {
+                valid
+                    .iter()
+                    .any(|&(j, t)| j != i && !redundant.contains(&j) && t.covers(m))
+            });
+        if others_cover {
+            redundant.push(i);
+        }
+    }
+
+    let terms: Vec<Implicant> = valid.iter().map(|&(_, t)| t).collect();
+    let unused = solution
+        .cover
+        .iter()
+        .filter(|t| !terms.contains(t))
+        .copied()
+        .collect();
+
+    Review {
+        form: solution.form,
+        cost: Cost::of(&terms, solution.variables),
+        minimal_cost: solution.cost(),
+        groups,
+        uncovered,
+        redundant,
+        unused,
+    }
+}
+
+fn list(cells: &[u32]) -> String {
+    cells
+        .iter()
+        .map(u32::to_string)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+fn sop(minterms: &[u32], dont_cares: &[u32], variables: usize) -> Solution {
+    use crate::logic::{solver::minimize, truth_table::TruthTable, variable::BitValue};
+
+    let names: Vec<String> = (0..variables)
+        .map(|i| ((b'A' + i as u8) as char).to_string())
+        .collect();
+    let mut table = TruthTable::new(&names, &["F"]);
+    for row in 0..table.rows.len() as u32 {
+        let value = if minterms.contains(&row) {
+            BitValue::One
+        } else if dont_cares.contains(&row) {
+            BitValue::DontCare
+        } else {
+            BitValue::Zero
+        };
+        table.set(row as usize, 0, value);
+    }
+    minimize(&table, 0, Form::Sop)
+}
+
+#[test]
+fn checks_hand_drawn_groups() {
+    // F(A,B,C,D) = Σm(0,2,5,7,8,10,13) + d(15): B'D' in the corners and BD
+    let solution = sop(&[0, 2, 5, 7, 8, 10, 13], &[15], 4);
+
+    let corners = check_group(&[0, 2, 8, 10], &solution);
+    assert!(corners.issues.is_empty());
+    assert_eq!(
+        corners.implicant,
+        Some(Implicant {
+            value: 0,
+            mask: 0b1010
+        })
+    );
+
+    assert_eq!(
+        check_group(&[0, 2, 8], &solution).issues,
+        vec![GroupIssue::NotPowerOfTwo(3)]
+    );
+    assert_eq!(
+        check_group(&[0, 5], &solution).issues,
+        vec![GroupIssue::NotAdjacent]
+    );
+    assert_eq!(
+        check_group(&[0, 1], &solution).issues,
+        vec![GroupIssue::CoversOff(vec![1])]
+    );
+    // 13 and the don't care at 15 let 5 and 7 grow into BD
+    assert_eq!(
+        check_group(&[5, 7], &solution).issues,
+        vec![GroupIssue::NotPrime(Implicant {
+            value: 0b0101,
+            mask: 0b1010
+        })]
+    );
+}
+
+#[test]
+fn reviews_covers_against_the_minimum() {
+    let solution = sop(&[0, 2, 5, 7, 8, 10, 13], &[15], 4);
+
+    let minimal = review(&[vec![0, 2, 8, 10], vec![5, 7, 13, 15]], &solution);
+    assert!(minimal.is_minimal());
+    assert_eq!(
+        minimal.messages(&[], Notation::Prime),
+        vec!["Your cover is minimal".to_string()]
+    );
+
+    let partial = review(&[vec![0, 2], vec![0, 2, 8, 10], vec![5, 7]], &solution);
+    assert!(!partial.is_minimal());
+    assert_eq!(partial.uncovered, vec![13]);
+    assert_eq!(partial.redundant, vec![0]);
+    assert_eq!(partial.unused.len(), 1);
+
+    let missing = review(&[vec![0, 2, 8, 10]], &solution);
+    assert_eq!(missing.uncovered, vec![5, 7, 13]);
+
+    // Terms follow the chosen notation
+    let names: Vec<String> = ["A", "B", "C", "D"].map(String::from).to_vec();
+    let grow = review(&[vec![0, 2, 8, 10], vec![5, 7], vec![13, 15]], &solution);
+    assert_eq!(
+        grow.messages(&names, Notation::C)[0],
+        "Group 2: not prime, it can grow to B && D"
+    );
+}
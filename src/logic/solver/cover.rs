@@ -0,0 +1,417 @@
+use std::cmp::Reverse;
+
+use super::{Cost, Implicant};
+
+/// Work [`minimum_covers`] may spend finding the minimal cost, in branches
+/// times the size of the chart since every branch scans it
+const WORK_LIMIT: usize = 16_000_000;
+
+/// Work [`minimum_covers`] may spend listing the covers that tie for it
+const TIE_WORK_LIMIT: usize = WORK_LIMIT / 5;
+
+/// Most minimal covers [`minimum_covers`] lists for one function
+pub const SOLUTION_LIMIT: usize = 64;
+
+/// Petrick's product of sums for the minterms the essentials leave uncovered
+struct Core {
+    candidates: Vec<Implicant>,
+    literals: Vec<usize>,
+    /// One sum per uncovered minterm, the candidates covering it with the
+    /// cheapest first
+    sums: Vec<Vec<usize>>,
+    /// The sums each candidate appears in
+    appears: Vec<Vec<usize>>,
+}
+
+impl Core {
+    fn new(
+        primes: &[Implicant],
+        essential: &[Implicant],
+        minterms: &[u32],
+        variables: usize,
+    ) -> Self {
+        let candidates: Vec<Implicant> = primes
+            .iter()
+            .copied()
+            .filter(|p| !essential.contains(p))
+            .collect();
+        let literals: Vec<usize> = candidates
+            .iter()
+            .map(|p| p.literal_count(variables))
+            .collect();
+
+        let mut sums: Vec<Vec<usize>> = minterms
+            .iter()
+            .filter(|&&m| !essential.iter().any(|p| p.covers(m)))
+            .map(|&m| {
+                (0..candidates.len())
+                    .filter(|&i| candidates[i].covers(m))
+                    .collect()
+            })
+            .collect();
+
+        // A sum containing another one adds nothing, X(X + Y) = X
+        sums.sort_by_key(Vec::len);
+        let mut kept: Vec<Vec<usize>> = Vec::new();
+        for sum in sums {
+            if !kept.iter().any(|k| k.iter().all(|i| sum.contains(i))) {
+                kept.push(sum);
+            }
+        }
+
+        let mut appears = vec![Vec::new(); candidates.len()];
+        for (s, sum) in kept.iter().enumerate() {
+            for &i in sum {
+                appears[i].push(s);
+            }
+        }
+        for sum in &mut kept {
+            sum.sort_by_key(|&i| (literals[i], Reverse(appears[i].len())));
+        }
+
+        Self {
+            candidates,
+            literals,
+            sums: kept,
+            appears,
+        }
+    }
+}
+
+#[derive(Default)]
+struct Undo {
+    taken: Vec<usize>,
+    banned: Vec<usize>,
+}
+
+/// Depth-first expansion of the product of sums, one sum at a time
+///
+/// Each branch takes one candidate of the sum with the fewest left and rules
+/// out the candidates tried before it, so no product is reached twice.
+/// Branches whose lower bound can't match the best cost are cut.
+struct Search<'a> {
+    core: &'a Core,
+    /// How many chosen candidates each sum contains
+    hits: Vec<u32>,
+    banned: Vec<bool>,
+    chosen: Vec<usize>,
+    cost: Cost,
+    best: Cost,
+    /// Keep products as cheap as `best`, rather than only cheaper ones
+    ties: bool,
+    found: Vec<Vec<usize>>,
+    nodes: usize,
+    /// Most branches explored before giving up
+    limit: usize,
+    /// Marks candidates already counted by the current lower bound
+    seen: Vec<usize>,
+}
+
+impl<'a> Search<'a> {
+    fn new(core: &'a Core, best: Cost, ties: bool) -> Self {
+        Self {
+            core,
+            hits: vec![0; core.sums.len()],
+            banned: vec![false; core.candidates.len()],
+            chosen: Vec::new(),
+            cost: Cost::default(),
+            best,
+            ties,
+            found: Vec::new(),
+            nodes: 0,
+            limit: if ties { TIE_WORK_LIMIT } else { WORK_LIMIT }
+                / (core.sums.len() + core.candidates.len() + 1),
+            seen: vec![0; core.candidates.len()],
+        }
+    }
+
+    /// Explores the products below the current one, `false` once out of budget
+    fn run(&mut self) -> bool {
+        self.nodes += 1;
+        if self.nodes > self.limit || self.found.len() >= SOLUTION_LIMIT {
+            return false;
+        }
+
+        let undo = self.reduce();
+        let within = self.branch();
+        self.restore(undo);
+        within
+    }
+
+    fn restore(&mut self, undo: Undo) {
+        for i in undo.taken.into_iter().rev() {
+            self.take(i, false);
+        }
+        for i in undo.banned {
+            self.banned[i] = false;
+        }
+    }
+
+    fn branch(&mut self) -> bool {
+        let Some((bound, narrowest)) = self.lower_bound() else {
+            return true;
+        };
+        if bound > self.best || (!self.ties && bound == self.best) {
+            return true;
+        }
+
+        let Some(open) = narrowest else {
+            if self.cost < self.best {
+                self.best = self.cost;
+                self.found.clear();
+            }
+            self.found.push(self.chosen.clone());
+            return true;
+        };
+
+        let mut tried = Vec::new();
+        let mut within = true;
+        for &i in &self.core.sums[open] {
+            if self.banned[i] {
+                continue;
+            }
+
+            self.take(i, true);
+            within = self.run();
+            self.take(i, false);
+            if !within {
+                break;
+            }
+
+            self.banned[i] = true;
+            tried.push(i);
+        }
+        for i in tried {
+            self.banned[i] = false;
+        }
+        within
+    }
+
+    /// Takes the candidates left alone in a sum and rules out dominated ones
+    /// until the chart stops changing
+    ///
+    /// A candidate is dominated by another with fewer literals that appears in
+    /// every open sum it does. Without `ties` an equally cheap one is enough,
+    /// which can drop covers that tie for the minimum but never the minimum
+    /// itself.
+    fn reduce(&mut self) -> Undo {
+        let core = self.core;
+        let mut undo = Undo::default();
+        loop {
+            let mut changed = false;
+
+            for s in 0..core.sums.len() {
+                if self.hits[s] > 0 {
+                    continue;
+                }
+                let mut open = core.sums[s].iter().filter(|&&i| !self.banned[i]);
+                if let (Some(&i), None) = (open.next(), open.next()) {
+                    self.take(i, true);
+                    undo.taken.push(i);
+                    changed = true;
+                }
+            }
+
+            let mut open = Vec::new();
+            for i in 0..core.candidates.len() {
+                if self.banned[i] {
+                    continue;
+                }
+                open.clear();
+                open.extend(
+                    core.appears[i]
+                        .iter()
+                        .copied()
+                        .filter(|&s| self.hits[s] == 0),
+                );
+                // Any dominating candidate is in the narrowest of these sums
+                let Some(&narrowest) = open.iter().min_by_key(|&&s| core.sums[s].len()) else {
+                    continue;
+                };
+
+                let open_count = |j: usize| {
+                    core.appears[j]
+                        .iter()
+                        .filter(|&&s| self.hits[s] == 0)
+                        .count()
+                };
+                let dominated = core.sums[narrowest].iter().any(|&j| {
+                    let (a, b) = (core.literals[i], core.literals[j]);
+                    let cheaper =
+                        b < a || !self.ties && b == a && (j < i || open_count(j) > open.len());
+                    j != i
+                        && !self.banned[j]
+                        && cheaper
+                        && open.iter().all(|&s| core.sums[s].contains(&j))
+                });
+                if dominated {
+                    self.banned[i] = true;
+                    undo.banned.push(i);
+                    changed = true;
+                }
+            }
+
+            if !changed {
+                return undo;
+            }
+        }
+    }
+
+    /// Adds or removes candidate `i` from the current product
+    fn take(&mut self, i: usize, add: bool) {
+        let literals = self.core.literals[i];
+        for &s in &self.core.appears[i] {
+            if add {
+                self.hits[s] += 1;
+            } else {
+                self.hits[s] -= 1;
+            }
+        }
+        if add {
+            self.chosen.push(i);
+            self.cost.terms += 1;
+            self.cost.literals += literals;
+        } else {
+            self.chosen.pop();
+            self.cost.terms -= 1;
+            self.cost.literals -= literals;
+        }
+    }
+
+    /// Cost of the current product plus one candidate for each open sum that
+    /// shares no candidate with the others counted, `None` if a sum can no
+    /// longer be covered
+    fn lower_bound(&mut self) -> Option<(Cost, Option<usize>)> {
+        let core = self.core;
+        let mut open: Vec<(usize, usize)> = Vec::new();
+        for (s, sum) in core.sums.iter().enumerate() {
+            if self.hits[s] == 0 {
+                let left = sum.iter().filter(|&&i| !self.banned[i]).count();
+                if left == 0 {
+                    return None;
+                }
+                open.push((left, s));
+            }
+        }
+        // Sums with few candidates left are the likeliest to stay disjoint
+        open.sort_unstable();
+
+        let stamp = self.nodes;
+        let mut bound = self.cost;
+        for &(_, s) in &open {
+            let sum = &core.sums[s];
+            if sum.iter().any(|&i| self.seen[i] == stamp) {
+                continue;
+            }
+            let mut cheapest = None;
+            for &i in sum.iter().filter(|&&i| !self.banned[i]) {
+                cheapest.get_or_insert(core.literals[i]);
+                self.seen[i] = stamp;
+            }
+            bound.terms += 1;
+            bound.literals += cheapest.unwrap_or_default();
+        }
+        Some((bound, open.first().map(|&(_, s)| s)))
+    }
+}
+
+/// Solves the cyclic core left after the essential primes with Petrick's method
+///
+/// Each uncovered minterm becomes a sum of the primes covering it, and sums
+/// containing another sum are dropped. The product of the rest is expanded
+/// depth first with branch and bound: a first pass finds the minimal
+/// [`Cost`], starting from [`greedy_cover`], and a second lists every product
+/// of that cost. Covers come back sorted so the result is deterministic.
+///
+/// Returns `None` if the first pass needs more than [`WORK_LIMIT`],
+/// callers then fall back to a heuristic cover. If only the second pass runs
+/// out, or more than [`SOLUTION_LIMIT`] covers tie, the ones found so far are
+/// returned.
+pub(super) fn minimum_covers(
+    primes: &[Implicant],
+    essential: &[Implicant],
+    minterms: &[u32],
+    variables: usize,
+) -> Option<Vec<Vec<Implicant>>> {
+    let core = Core::new(primes, essential, minterms, variables);
+
+    // The search only counts what it adds to the essentials
+    let greedy: Vec<Implicant> = greedy_cover(primes, essential, minterms, variables)
+        .into_iter()
+        .filter(|p| !essential.contains(p))
+        .collect();
+    let mut first = Search::new(&core, Cost::of(&greedy, variables), false);
+    if !first.run() {
+        return None;
+    }
+
+    let mut second = Search::new(&core, first.best, true);
+    second.run();
+
+    // Ties the second pass didn't reach in time still include the first
+    // minimum found, or the greedy cover if nothing beat it
+    let mut found = second.found;
+    if found.is_empty() {
+        found = first.found;
+    }
+    let mut solutions: Vec<Vec<Implicant>> = if found.is_empty() {
+        vec![greedy]
+    } else {
+        found
+            .into_iter()
+            .map(|product| product.into_iter().map(|i| core.candidates[i]).collect())
+            .collect()
+    };
+    for cover in &mut solutions {
+        cover.extend_from_slice(essential);
+        cover.sort();
+    }
+    solutions.sort();
+    Some(solutions)
+}
+
+/// Completes the essentials greedily, each step taking the prime that covers
+/// the most remaining minterms with the fewest literals
+///
+/// Stops early if no prime covers what is left, so the result only covers
+/// every minterm when `primes` do.
+pub(super) fn greedy_cover(
+    primes: &[Implicant],
+    essential: &[Implicant],
+    minterms: &[u32],
+    variables: usize,
+) -> Vec<Implicant> {
+    let mut cover = essential.to_vec();
+    let mut uncovered: Vec<u32> = minterms
+        .iter()
+        .copied()
+        .filter(|&m| !cover.iter().any(|p| p.covers(m)))
+        .collect();
+
+    while let Some((hits, best)) = primes
+        .iter()
+        .map(|p| (uncovered.iter().filter(|&&m| p.covers(m)).count(), p))
+        .max_by_key(|&(hits, p)| (hits, Reverse(p.literal_count(variables))))
+        && hits > 0
+    {
+        uncovered.retain(|&m| !best.covers(m));
+        cover.push(*best);
+    }
+
+    cover.sort();
+    cover
+}
+
+/// One cover picked from `primes`, minimal when [`minimum_covers`] finishes
+/// and otherwise from [`greedy_cover`]
+pub(super) fn select_cover(
+    primes: &[Implicant],
+    essential: &[Implicant],
+    minterms: &[u32],
+    variables: usize,
+) -> Vec<Implicant> {
+    match minimum_covers(primes, essential, minterms, variables) {
+        Some(solutions) => solutions.into_iter().next().unwrap_or_default(),
+        None => greedy_cover(primes, essential, minterms, variables),
+    }
+}
@@ -0,0 +1,243 @@
+use super::{Cost, Implicant};
+
+/// Cell state as seen by the heuristic, independent of SOP/POS polarity
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Cell {
+    On,
+    Off,
+    DontCare,
+}
+
+/// The function being minimized, stored as one [`Cell`] per minterm
+struct Function {
+    variables: usize,
+    cells: Vec<Cell>,
+}
+
+impl Function {
+    fn new(minterms: &[u32], dont_cares: &[u32], variables: usize) -> Self {
+        let mut cells = vec![Cell::Off; 1 << variables];
+        for &m in minterms {
+            cells[m as usize] = Cell::On;
+        }
+        for &m in dont_cares {
+            cells[m as usize] = Cell::DontCare;
+        }
+
+        Self { variables, cells }
+    }
+
+    /// A cube is a valid implicant if it never touches the OFF-set
+    fn is_implicant(&self, cube: &Implicant) -> bool {
+        cube.minterms()
+            .iter()
+            .all(|&m| self.cells[m as usize] != Cell::Off)
+    }
+
+    fn on_minterms(&self, cube: &Implicant) -> Vec<u32> {
+        cube.minterms()
+            .into_iter()
+            .filter(|&m| self.cells[m as usize] == Cell::On)
+            .collect()
+    }
+}
+
+/// Heuristically minimizes a function in the style of Espresso
+///
+/// Starting from one cube per minterm, the cover is repeatedly passed through
+/// EXPAND (grow every cube into a prime), IRREDUNDANT (drop cubes covered by the
+/// rest) and REDUCE (shrink cubes to the minterms only they cover) until the
+/// [`Cost`] stops improving. The result is a prime, irredundant cover, but not
+/// necessarily a minimum one.
+///
+/// Unlike real Espresso, which works on cube lists, every cell of the function
+/// is stored, so memory and the cost of each implicant check grow with
+/// `1 << variables`. That is only affordable because tables are capped at
+/// [`MAX_VARIABLES`](crate::logic::gray::MAX_VARIABLES) inputs.
+///
+/// ### Parameters
+/// - `minterms`: Cells that must be covered
+/// - `dont_cares`: Cells that may be covered
+/// - `variables`: Number of inputs of the function
+pub fn minimize_cover(minterms: &[u32], dont_cares: &[u32], variables: usize) -> Vec<Implicant> {
+    let function = Function::new(minterms, dont_cares, variables);
+
+    let cover: Vec<Implicant> = minterms
+        .iter()
+        .copied()
+        .map(Implicant::from_minterm)
+        .collect();
+    let mut cover = irredundant(&function, expand(&function, cover));
+    let mut best = cover.clone();
+
+    loop {
+        cover = irredundant(&function, expand(&function, reduce(&function, cover)));
+
+        if Cost::of(&cover, variables) < Cost::of(&best, variables) {
+            best = cover.clone();
+        } else {
+            break;
+        }
+    }
+
+    best.sort();
+    best
+}
+
+/// Grows each cube into a prime and drops the cubes it swallows
+///
+/// Larger cubes are expanded first. Each step raises the literal that keeps the
+/// cube an implicant while containing the most cubes still waiting to expand.
+fn expand(function: &Function, mut cover: Vec<Implicant>) -> Vec<Implicant> {
+    cover.sort_by_key(|c| std::cmp::Reverse(c.mask.count_ones()));
+
+    let mut out: Vec<Implicant> = Vec::new();
+    for (i, &cube) in cover.iter().enumerate() {
+        if out.iter().any(|c| contains(c, &cube)) {
+            continue;
+        }
+
+        let pending = &cover[i + 1..];
+        let mut cube = cube;
+        loop {
+            let best = (0..function.variables)
+                .map(|i| 1u32 << i)
+                .filter(|bit| cube.mask & bit == 0)
+                .map(|bit| Implicant {
+                    value: cube.value & !bit,
+                    mask: cube.mask | bit,
+                })
+                .filter(|raised| function.is_implicant(raised))
+                .max_by_key(|raised| pending.iter().filter(|c| contains(raised, c)).count());
+
+            match best {
+                Some(raised) => cube = raised,
+                None => break,
+            }
+        }
+
+        out.retain(|c| !contains(&cube, c));
+        out.push(cube);
+    }
+
+    out
+}
+
+/// Removes cubes whose ON minterms are all covered by the remaining cubes
+///
+/// Smaller cubes are tried first since they are the cheapest to lose.
+fn irredundant(function: &Function, mut cover: Vec<Implicant>) -> Vec<Implicant> {
+    let mut counts = vec![0u32; function.cells.len()];
+    for cube in &cover {
+        for m in function.on_minterms(cube) {
+            counts[m as usize] += 1;
+        }
+    }
+
+    cover.sort_by_key(|c| c.mask.count_ones());
+
+    let mut out = Vec::new();
+    for cube in cover {
+        let on = function.on_minterms(&cube);
+        if on.iter().all(|&m| counts[m as usize] > 1) {
+            for m in on {
+                counts[m as usize] -= 1;
+            }
+        } else {
+            out.push(cube);
+        }
+    }
+
+    out
+}
+
+/// Shrinks each cube to the smallest cube holding the minterms only it covers
+fn reduce(function: &Function, cover: Vec<Implicant>) -> Vec<Implicant> {
+    let mut cover = cover;
+    let mut i = 0;
+
+    while i < cover.len() {
+        let unique: Vec<u32> = function
+            .on_minterms(&cover[i])
+            .into_iter()
+            .filter(|&m| !cover.iter().enumerate().any(|(j, c)| j != i && c.covers(m)))
+            .collect();
+
+        match supercube(&unique) {
+            Some(cube) => {
+                cover[i] = cube;
+                i += 1;
+            }
+            None => {
+                cover.remove(i);
+            }
+        }
+    }
+
+    cover
+}
+
+/// The smallest cube containing every minterm in `minterms`
+fn supercube(minterms: &[u32]) -> Option<Implicant> {
+    let (&first, rest) = minterms.split_first()?;
+    let mask = rest.iter().fold(0, |mask, &m| mask | (m ^ first));
+
+    Some(Implicant {
+        value: first & !mask,
+        mask,
+    })
+}
+
+/// Whether cube `outer` contains every minterm of `inner`
+fn contains(outer: &Implicant, inner: &Implicant) -> bool {
+    inner.mask & !outer.mask == 0 && outer.covers(inner.value)
+}
+
+#[test]
+fn matches_exact_cost_on_small_functions() {
+    // F(A,B,C,D) = Σm(4,8,10,11,12,15) + d(9,14)
+    let cover = minimize_cover(&[4, 8, 10, 11, 12, 15], &[9, 14], 4);
+    assert_eq!(
+        Cost::of(&cover, 4),
+        Cost {
+            terms: 3,
+            literals: 7
+        }
+    );
+
+    // F(A,B,C) = Σm(0,1,2,5,6,7), cyclic with no essential primes
+    let cover = minimize_cover(&[0, 1, 2, 5, 6, 7], &[], 3);
+    assert_eq!(
+        Cost::of(&cover, 3),
+        Cost {
+            terms: 3,
+            literals: 6
+        }
+    );
+}
+
+#[test]
+fn handles_wide_functions() {
+    // F = AB + C'D + EFG over 14 inputs, the rest unused
+    let variables = 14;
+    let bit = |m: u32, i: usize| m >> (variables - 1 - i) & 1 == 1;
+    let minterms: Vec<u32> = (0..1u32 << variables)
+        .filter(|&m| {
+            (bit(m, 0) && bit(m, 1))
+                || (!bit(m, 2) && bit(m, 3))
+                || (bit(m, 4) && bit(m, 5) && bit(m, 6))
+        })
+        .collect();
+
+    let cover = minimize_cover(&minterms, &[], variables);
+    assert_eq!(
+        Cost::of(&cover, variables),
+        Cost {
+            terms: 3,
+            literals: 7
+        }
+    );
+    for &m in &minterms {
+        assert!(cover.iter().any(|c| c.covers(m)));
+    }
+}
@@ -0,0 +1,190 @@
+use super::{Implicant, Method, Solution};
+
+/// A static hazard between two adjacent cells of the function
+///
+/// Both cells hold the covered value (1 for SOP, 0 for POS) and differ only
+/// in `variable`, yet no single term of the cover contains both, so the
+/// output may glitch while that input changes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Hazard {
+    /// The smaller of the two row numbers
+    pub from: u32,
+    pub to: u32,
+    /// Index of the input that changes, first input first
+    pub variable: usize,
+}
+
+/// Finds every pair of adjacent `minterms` that no term of `cover` spans
+///
+/// Don't-care cells are never part of a hazard since the output is
+/// unspecified there.
+///
+/// ### Examples
+/// ```
+/// # use karny::logic::solver::{Implicant, hazard::find_hazards};
+/// // F(A,B,C) = AB' + BC glitches at A=1, C=1 while B changes
+/// let cover = [
+///     Implicant { value: 0b100, mask: 0b001 },
+///     Implicant { value: 0b011, mask: 0b100 },
+/// ];
+/// let hazards = find_hazards(&cover, &[3, 4, 5, 7], 3);
+/// assert_eq!((hazards[0].from, hazards[0].to), (5, 7));
+/// ```
+pub fn find_hazards(cover: &[Implicant], minterms: &[u32], variables: usize) -> Vec<Hazard> {
+    let mut hazards = Vec::new();
+    for &from in minterms {
+        for variable in 0..variables {
+            let bit = 1 << (variables - 1 - variable);
+            let to = from | bit;
+            if to == from || minterms.binary_search(&to).is_err() {
+                continue;
+            }
+
+            if !cover.iter().any(|t| t.covers(from) && t.covers(to)) {
+                hazards.push(Hazard { from, to, variable });
+            }
+        }
+    }
+    hazards.sort();
+    hazards
+}
+
+impl Solution {
+    /// Static hazards of the selected cover, static-1 for [`Form::Sop`] and
+    /// static-0 for [`Form::Pos`]
+    ///
+    /// [`Form::Sop`]: super::Form::Sop
+    /// [`Form::Pos`]: super::Form::Pos
+    pub fn hazards(&self) -> Vec<Hazard> {
+        find_hazards(&self.cover, &self.minterms, self.variables)
+    }
+
+    /// The selected cover plus one consensus term per hazard in `hazards`,
+    /// as found by [`Solution::hazards`]
+    ///
+    /// Each added term is a prime implicant spanning the hazard's two cells,
+    /// so the result has no static hazards. Exact solutions already know all
+    /// their primes and add the one with the fewest literals; heuristic ones,
+    /// and any solution without [`Solution::prime_implicants`], grow the pair
+    /// into a prime instead of generating every prime again.
+    pub fn hazard_free_cover(&self, hazards: &[Hazard]) -> Vec<Implicant> {
+        let mut cover = self.cover.clone();
+        for hazard in hazards {
+            if cover
+                .iter()
+                .any(|t| t.covers(hazard.from) && t.covers(hazard.to))
+            {
+                continue;
+            }
+
+            let consensus = match self.method {
+                Method::Heuristic => self.expand_pair(hazard),
+                _ => self
+                    .prime_implicants
+                    .iter()
+                    .filter(|p| p.covers(hazard.from) && p.covers(hazard.to))
+                    .max_by_key(|p| p.mask.count_ones())
+                    .copied()
+                    // Solutions built by hand may not list their primes
+                    .unwrap_or_else(|| self.expand_pair(hazard)),
+            };
+            cover.push(consensus);
+        }
+        cover
+    }
+
+    /// Grows the term spanning a hazard's two cells one variable at a time,
+    /// first input first, for as long as it stays within the function
+    fn expand_pair(&self, hazard: &Hazard) -> Implicant {
+        let allowed = |m: u32| {
+            self.minterms.binary_search(&m).is_ok() || self.dont_cares.binary_search(&m).is_ok()
+        };
+
+        let bit = hazard.from ^ hazard.to;
+        let mut term = Implicant {
+            value: hazard.from & !bit,
+            mask: bit,
+        };
+        for variable in 0..self.variables {
+            let bit = 1 << (self.variables - 1 - variable);
+            if term.mask & bit != 0 {
+                continue;
+            }
+
+            let grown = Implicant {
+                value: term.value & !bit,
+                mask: term.mask | bit,
+            };
+            if grown.minterms().into_iter().all(allowed) {
+                term = grown;
+            }
+        }
+        term
+    }
+}
+
+#[test]
+fn finds_static_one_hazards_in_sop_covers() {
+    use super::{Form, minimize, minimize_with, table_from};
+
+    // F(A,B,C) = A'B + AC, the textbook static-1 hazard at B = C = 1
+    let table = table_from(&["A", "B", "C"], &[2, 3, 5, 7], &[]);
+    let solution = minimize(&table, 0, Form::Sop);
+    assert_eq!(
+        solution.hazards(),
+        vec![Hazard {
+            from: 3,
+            to: 7,
+            variable: 0
+        }]
+    );
+
+    let cover = solution.hazard_free_cover(&solution.hazards());
+    assert_eq!(cover.len(), 3);
+    assert!(cover.contains(&Implicant {
+        value: 0b011,
+        mask: 0b100
+    }));
+    assert!(find_hazards(&cover, &solution.minterms, 3).is_empty());
+
+    // The heuristic cover has no chart of primes, the pair is grown into B C
+    let heuristic = minimize_with(&table, 0, Form::Sop, Method::Heuristic);
+    let cover = heuristic.hazard_free_cover(&heuristic.hazards());
+    assert!(cover.contains(&Implicant {
+        value: 0b011,
+        mask: 0b100
+    }));
+
+    // Nor does a solution built without one
+    let bare = Solution {
+        prime_implicants: Vec::new(),
+        ..solution.clone()
+    };
+    let cover = bare.hazard_free_cover(&bare.hazards());
+    assert!(cover.contains(&Implicant {
+        value: 0b011,
+        mask: 0b100
+    }));
+}
+
+#[test]
+fn finds_static_zero_hazards_in_pos_covers() {
+    use super::{Form, minimize, table_from};
+
+    // F(A,B,C) = (A + B)(A' + C), zeros at 0, 1, 4 and 6
+    let table = table_from(&["A", "B", "C"], &[2, 3, 5, 7], &[]);
+    let solution = minimize(&table, 0, Form::Pos);
+    assert_eq!(solution.hazards().len(), 1);
+    assert!(
+        find_hazards(
+            &solution.hazard_free_cover(&solution.hazards()),
+            &solution.minterms,
+            solution.variables
+        )
+        .is_empty()
+    );
+
+    // A single group has nothing to glitch between
+    let table = table_from(&["A", "B"], &[2, 3], &[]);
+    assert!(minimize(&table, 0, Form::Sop).hazards().is_empty());
+}
@@ -0,0 +1,621 @@
+mod cover;
+mod espresso;
+pub mod hazard;
+pub mod multi;
+pub mod tableau;
+
+pub use cover::SOLUTION_LIMIT;
+use cover::{minimum_covers, select_cover};
+
+use crate::logic::{truth_table::TruthTable, variable::BitValue};
+
+/// A product term produced by the Quine–McCluskey method
+///
+/// Bits set in `mask` mark variables that were eliminated while merging; the
+/// remaining bits of `value` hold the polarity of each literal. Bit `n - 1`
+/// belongs to the first input, matching the row order of [`TruthTable`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Implicant {
+    pub value: u32,
+    pub mask: u32,
+}
+
+impl Implicant {
+    pub fn from_minterm(minterm: u32) -> Self {
+        Self {
+            value: minterm,
+            mask: 0,
+        }
+    }
+
+    /// Merges two implicants that differ in exactly one fixed literal
+    ///
+    /// ### Examples
+    /// ```
+    /// # use karny::logic::solver::Implicant;
+    /// let a = Implicant::from_minterm(0b0101);
+    /// let b = Implicant::from_minterm(0b0111);
+    /// assert_eq!(a.combine(&b), Some(Implicant { value: 0b0101, mask: 0b0010 }));
+    /// ```
+    pub fn combine(&self, other: &Implicant) -> Option<Implicant> {
+        if self.mask != other.mask {
+            return None;
+        }
+
+        let diff = self.value ^ other.value;
+        if diff.count_ones() != 1 {
+            return None;
+        }
+
+        Some(Implicant {
+            value: self.value & !diff,
+            mask: self.mask | diff,
+        })
+    }
+
+    pub fn covers(&self, minterm: u32) -> bool {
+        minterm & !self.mask == self.value
+    }
+
+    /// Number of literals left in the product term for a function of `variables` inputs
+    pub fn literal_count(&self, variables: usize) -> usize {
+        variables - self.mask.count_ones() as usize
+    }
+
+    /// Every minterm covered by this implicant, in ascending order
+    pub fn minterms(&self) -> Vec<u32> {
+        let mut out = Vec::with_capacity(1 << self.mask.count_ones());
+        let mut sub = 0u32;
+        loop {
+            out.push(self.value | sub);
+            if sub == self.mask {
+                break;
+            }
+            sub = sub.wrapping_sub(self.mask) & self.mask;
+        }
+        out
+    }
+
+    /// The fixed literals as `(input index, value)` pairs, first input first
+    pub fn literals(&self, variables: usize) -> Vec<(usize, bool)> {
+        (0..variables)
+            .filter_map(|i| {
+                let bit = 1 << (variables - 1 - i);
+                (self.mask & bit == 0).then_some((i, self.value & bit != 0))
+            })
+            .collect()
+    }
+
+    /// Returns the implicant as a `1`/`0`/`-` string, first input first
+    ///
+    /// ### Examples
+    /// ```
+    /// # use karny::logic::solver::Implicant;
+    /// let term = Implicant { value: 0b100, mask: 0b001 };
+    /// assert_eq!(term.pattern(3), "10-");
+    /// ```
+    pub fn pattern(&self, variables: usize) -> String {
+        (0..variables)
+            .map(|i| {
+                let bit = 1 << (variables - 1 - i);
+                if self.mask & bit != 0 {
+                    '-'
+                } else if self.value & bit != 0 {
+                    '1'
+                } else {
+                    '0'
+                }
+            })
+            .collect()
+    }
+}
+
+/// How an implicant takes part in a minimized cover
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ImplicantRole {
+    /// The only prime covering at least one minterm, part of every solution
+    Essential,
+    /// Chosen to cover the cyclic core in the selected solution
+    Selected,
+    /// Not needed by the selected solution
+    Redundant,
+}
+
+/// The two-level form a solution is expressed in
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Form {
+    /// Sum of products, built by covering the ON cells
+    #[default]
+    Sop,
+    /// Product of sums, built by covering the OFF cells
+    Pos,
+}
+
+impl Form {
+    /// The cell value this form has to cover
+    pub fn target(self) -> BitValue {
+        match self {
+            Form::Sop => BitValue::One,
+            Form::Pos => BitValue::Zero,
+        }
+    }
+}
+
+/// Inputs above which [`Method::Auto`] goes straight to the heuristic minimizer
+///
+/// Up to here it tries the exact method first, which still gives up on
+/// functions too hard to solve quickly, so [`Solution::method`] is what says
+/// whether a cover is known to be minimal.
+pub const HEURISTIC_THRESHOLD: usize = 10;
+
+/// Most prime implicants the exact method charts before using the heuristic
+///
+/// Finding the primes stays fast up to [`crate::logic::gray::MAX_VARIABLES`],
+/// but charting tens of thousands of them against every minterm does not.
+const PRIME_LIMIT: usize = 4096;
+
+/// The algorithm used to produce a [`Solution`]
+///
+/// The exact method gives up on functions with thousands of primes or a
+/// cyclic core it can't search within budget, and returns the heuristic
+/// result instead. [`Solution::method`] records which one ran. In practice
+/// functions of up to 8 inputs are solved exactly even when dense, while
+/// dense functions of 9 or 10 inputs usually are not.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Method {
+    /// Exact up to [`HEURISTIC_THRESHOLD`] inputs when within budget,
+    /// heuristic otherwise
+    #[default]
+    Auto,
+    /// Quine–McCluskey followed by Petrick's method, always minimal when it
+    /// finishes within budget
+    Exact,
+    /// Espresso-style expand/irredundant/reduce, fast but not always minimal
+    Heuristic,
+}
+
+impl Method {
+    /// Replaces [`Method::Auto`] with the method used for `variables` inputs
+    pub fn resolve(self, variables: usize) -> Method {
+        match self {
+            Method::Auto if variables > HEURISTIC_THRESHOLD => Method::Heuristic,
+            Method::Auto => Method::Exact,
+            method => method,
+        }
+    }
+}
+
+/// Cost of a two-level expression, compared by term count and then literal count
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Cost {
+    pub terms: usize,
+    pub literals: usize,
+}
+
+impl Cost {
+    pub fn of(terms: &[Implicant], variables: usize) -> Self {
+        Self {
+            terms: terms.len(),
+            literals: terms.iter().map(|t| t.literal_count(variables)).sum(),
+        }
+    }
+}
+
+/// Result of minimizing a single output of a truth table
+///
+/// For [`Form::Pos`] every implicant describes a group of zeros, so `minterms`
+/// holds the maxterm numbers and each implicant becomes one sum term.
+///
+/// With [`Method::Heuristic`] only the primes of the final cover are known, so
+/// `prime_implicants` equals `cover`, `essential` lists the relatively
+/// essential terms and `solutions` holds that single cover.
+#[derive(Clone, Debug, Default)]
+pub struct Solution {
+    pub form: Form,
+    pub method: Method,
+    pub variables: usize,
+
+    pub minterms: Vec<u32>,
+    pub dont_cares: Vec<u32>,
+
+    /// Every prime implicant that covers at least one minterm
+    pub prime_implicants: Vec<Implicant>,
+    /// Primes that are the sole cover of some minterm
+    pub essential: Vec<Implicant>,
+    /// Every cover of minimal [`Cost`], essentials included
+    ///
+    /// Functions with a great many equally cheap covers only list the ones
+    /// found within budget, at most [`SOLUTION_LIMIT`].
+    pub solutions: Vec<Vec<Implicant>>,
+    /// The selected solution, equal to `solutions[0]`
+    pub cover: Vec<Implicant>,
+}
+
+impl Solution {
+    pub fn cost(&self) -> Cost {
+        Cost::of(&self.cover, self.variables)
+    }
+
+    /// Classifies `implicant` relative to the selected cover
+    pub fn role(&self, implicant: &Implicant) -> ImplicantRole {
+        if self.essential.contains(implicant) {
+            ImplicantRole::Essential
+        } else if self.cover.contains(implicant) {
+            ImplicantRole::Selected
+        } else {
+            ImplicantRole::Redundant
+        }
+    }
+
+    /// Pairs every prime implicant with its [`ImplicantRole`]
+    pub fn roles(&self) -> Vec<(Implicant, ImplicantRole)> {
+        self.prime_implicants
+            .iter()
+            .map(|p| (*p, self.role(p)))
+            .collect()
+    }
+}
+
+/// Minimizes one output of a truth table, choosing the method by input count
+///
+/// `BitValue::DontCare` cells are used as optional terms: they may be merged
+/// into larger groups but never have to be covered.
+///
+/// ### Parameters
+/// - `table`: The truth table holding the function
+/// - `output_index`: Index of the output column to minimize
+/// - `form`: Whether to build a sum-of-products or a product-of-sums
+pub fn minimize(table: &TruthTable, output_index: usize, form: Form) -> Solution {
+    minimize_with(table, output_index, form, Method::Auto)
+}
+
+/// Minimizes one output of a truth table with an explicit [`Method`]
+pub fn minimize_with(
+    table: &TruthTable,
+    output_index: usize,
+    form: Form,
+    method: Method,
+) -> Solution {
+    let variables = table.inputs.len();
+    let method = method.resolve(variables);
+    let minterms = table.terms(output_index, form.target());
+    let dont_cares = table.terms(output_index, BitValue::DontCare);
+
+    let (prime_implicants, essential, solutions) = match method {
+        Method::Heuristic => {
+            let cover = espresso::minimize_cover(&minterms, &dont_cares, variables);
+            let essential = essential_primes(&cover, &minterms);
+            (cover.clone(), essential, vec![cover])
+        }
+        _ => {
+            let primes = prime_implicants(&minterms, &dont_cares);
+            if primes.len() > PRIME_LIMIT {
+                return minimize_with(table, output_index, form, Method::Heuristic);
+            }
+            let essential = essential_primes(&primes, &minterms);
+            match minimum_covers(&primes, &essential, &minterms, variables) {
+                Some(solutions) => (primes, essential, solutions),
+                None => return minimize_with(table, output_index, form, Method::Heuristic),
+            }
+        }
+    };
+    let cover = solutions.first().cloned().unwrap_or_default();
+
+    Solution {
+        form,
+        method,
+        variables,
+        minterms,
+        dont_cares,
+        prime_implicants,
+        essential,
+        solutions,
+        cover,
+    }
+}
+
+/// Minimal SOP and POS solutions for the same output
+#[derive(Clone, Debug)]
+pub struct Comparison {
+    pub sop: Solution,
+    pub pos: Solution,
+}
+
+impl Comparison {
+    /// The form with the lower [`Cost`], preferring SOP on a tie
+    pub fn cheaper(&self) -> Form {
+        if self.pos.cost() < self.sop.cost() {
+            Form::Pos
+        } else {
+            Form::Sop
+        }
+    }
+}
+
+/// Minimizes an output in both forms so their costs can be compared
+pub fn compare(table: &TruthTable, output_index: usize) -> Comparison {
+    Comparison {
+        sop: minimize(table, output_index, Form::Sop),
+        pos: minimize(table, output_index, Form::Pos),
+    }
+}
+
+/// Runs [`compare`] for every output of the table
+pub fn compare_all(table: &TruthTable) -> Vec<Comparison> {
+    (0..table.outputs.len())
+        .map(|i| compare(table, i))
+        .collect()
+}
+
+/// Finds all prime implicants of the function given by `minterms`
+///
+/// Runs the merging rounds of [`tableau::merge_rounds`] and keeps the terms
+/// that never combined. Primes made up entirely of don't-cares are dropped
+/// since they never need to be selected.
+pub fn prime_implicants(minterms: &[u32], dont_cares: &[u32]) -> Vec<Implicant> {
+    tableau::unmerged(&tableau::merge_rounds(minterms, dont_cares), minterms)
+}
+
+/// Primes that are the only implicant covering some minterm
+fn essential_primes(primes: &[Implicant], minterms: &[u32]) -> Vec<Implicant> {
+    let mut essential: Vec<Implicant> = Vec::new();
+
+    for &m in minterms {
+        let mut covering = primes.iter().filter(|p| p.covers(m));
+        if let (Some(p), None) = (covering.next(), covering.next())
+            && !essential.contains(p)
+        {
+            essential.push(*p);
+        }
+    }
+
+    essential.sort();
+    essential
+}
+
+#[cfg(test)]
+fn table_from(variables: &[&str], ones: &[u32], dont_cares: &[u32]) -> TruthTable {
+    let mut table = TruthTable::new(variables, &["F"]);
+    for row in 0..table.rows.len() {
+        let value = if ones.contains(&(row as u32)) {
+            BitValue::One
+        } else if dont_cares.contains(&(row as u32)) {
+            BitValue::DontCare
+        } else {
+            BitValue::Zero
+        };
+        table.set(row, 0, value);
+    }
+    table
+}
+
+/// A fixed pseudo-random function with about half its cells set to 1 and an
+/// eighth left as don't cares
+#[cfg(test)]
+fn random_table(variables: usize, mut seed: u32) -> TruthTable {
+    let mut next = || {
+        seed = seed.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+        seed >> 16
+    };
+    let (mut ones, mut dont_cares) = (Vec::new(), Vec::new());
+    for m in 0..1 << variables {
+        match next() % 8 {
+            0..4 => ones.push(m),
+            4 => dont_cares.push(m),
+            _ => {}
+        }
+    }
+
+    let names: Vec<String> = (0..variables).map(|i| format!("X{i}")).collect();
+    let names: Vec<&str> = names.iter().map(String::as_str).collect();
+    table_from(&names, &ones, &dont_cares)
+}
+
+/// Checks that the cover takes in every minterm and nothing outside the
+/// function
+#[cfg(test)]
+fn assert_covers(table: &TruthTable, solution: &Solution) {
+    let allowed = table.terms(0, BitValue::DontCare);
+    for &m in &solution.minterms {
+        assert!(solution.cover.iter().any(|p| p.covers(m)));
+    }
+    for term in &solution.cover {
+        for m in term.minterms() {
+            assert!(solution.minterms.contains(&m) || allowed.contains(&m));
+        }
+    }
+}
+
+#[cfg(test)]
+fn patterns(terms: &[Implicant], variables: usize) -> Vec<String> {
+    let mut out: Vec<String> = terms.iter().map(|t| t.pattern(variables)).collect();
+    out.sort();
+    out
+}
+
+#[test]
+fn finds_primes_with_dont_cares() {
+    // F(A,B,C,D) = Σm(4,8,10,11,12,15) + d(9,14)
+    let table = table_from(&["A", "B", "C", "D"], &[4, 8, 10, 11, 12, 15], &[9, 14]);
+    let solution = minimize(&table, 0, Form::Sop);
+
+    assert_eq!(
+        patterns(&solution.prime_implicants, 4),
+        vec!["-100", "1--0", "1-1-", "10--"]
+    );
+    assert_eq!(patterns(&solution.essential, 4), vec!["-100", "1-1-"]);
+
+    // AB' and AD' tie for minterm 8, so both covers are reported
+    assert_eq!(solution.solutions.len(), 2);
+    for cover in &solution.solutions {
+        assert_eq!(
+            Cost::of(cover, 4),
+            Cost {
+                terms: 3,
+                literals: 7
+            }
+        );
+    }
+
+    assert_eq!(
+        solution.role(&solution.essential[0]),
+        ImplicantRole::Essential
+    );
+    let unused = solution
+        .prime_implicants
+        .iter()
+        .find(|p| !solution.cover.contains(p))
+        .unwrap();
+    assert_eq!(solution.role(unused), ImplicantRole::Redundant);
+}
+
+#[test]
+fn minimizes_constant_functions() {
+    let zero = minimize(&table_from(&["A", "B"], &[], &[]), 0, Form::Sop);
+    assert!(zero.prime_implicants.is_empty());
+    assert!(zero.cover.is_empty());
+
+    let one = minimize(&table_from(&["A", "B"], &[0, 1, 2, 3], &[]), 0, Form::Sop);
+    assert_eq!(patterns(&one.cover, 2), vec!["--"]);
+}
+
+#[test]
+fn petrick_returns_every_minimal_cover() {
+    // F(A,B,C) = Σm(0,1,2,5,6,7), the classic cyclic function
+    let solution = minimize(
+        &table_from(&["A", "B", "C"], &[0, 1, 2, 5, 6, 7], &[]),
+        0,
+        Form::Sop,
+    );
+
+    assert_eq!(solution.prime_implicants.len(), 6);
+    assert!(solution.essential.is_empty());
+    assert_eq!(solution.solutions.len(), 2);
+
+    for cover in &solution.solutions {
+        assert_eq!(cover.len(), 3);
+        for &m in &solution.minterms {
+            assert!(cover.iter().any(|p| p.covers(m)));
+        }
+    }
+
+    assert!(
+        solution
+            .roles()
+            .iter()
+            .all(|(_, role)| *role != ImplicantRole::Essential)
+    );
+}
+
+#[test]
+fn minimizes_zeros_into_product_of_sums() {
+    // F(A,B,C) = Σm(1,3,5,6,7) = (A + C)(B + C)
+    let table = table_from(&["A", "B", "C"], &[1, 3, 5, 6, 7], &[]);
+    let pos = minimize(&table, 0, Form::Pos);
+
+    assert_eq!(pos.minterms, vec![0, 2, 4]);
+    assert_eq!(patterns(&pos.cover, 3), vec!["-00", "0-0"]);
+    assert_eq!(
+        pos.cost(),
+        Cost {
+            terms: 2,
+            literals: 4
+        }
+    );
+}
+
+#[test]
+fn compares_sop_and_pos_cost() {
+    // F(A,B,C) = Σm(1,3,5,6,7) = C + AB, cheaper as a sum-of-products
+    let sop_table = table_from(&["A", "B", "C"], &[1, 3, 5, 6, 7], &[]);
+    assert_eq!(compare(&sop_table, 0).cheaper(), Form::Sop);
+
+    // F(A,B,C,D) = (A + B)(C + D) needs four products but only two sums
+    let pos_table = table_from(
+        &["A", "B", "C", "D"],
+        &[5, 6, 7, 9, 10, 11, 13, 14, 15],
+        &[],
+    );
+    let comparison = compare(&pos_table, 0);
+    assert_eq!(
+        comparison.sop.cost(),
+        Cost {
+            terms: 4,
+            literals: 8
+        }
+    );
+    assert_eq!(
+        comparison.pos.cost(),
+        Cost {
+            terms: 2,
+            literals: 4
+        }
+    );
+    assert_eq!(comparison.cheaper(), Form::Pos);
+}
+
+#[test]
+fn auto_method_switches_above_threshold() {
+    assert_eq!(Method::Auto.resolve(HEURISTIC_THRESHOLD), Method::Exact);
+    assert_eq!(
+        Method::Auto.resolve(HEURISTIC_THRESHOLD + 1),
+        Method::Heuristic
+    );
+    assert_eq!(Method::Exact.resolve(16), Method::Exact);
+
+    let table = table_from(&["A", "B", "C"], &[0, 1, 2, 5, 6, 7], &[]);
+    let heuristic = minimize_with(&table, 0, Form::Sop, Method::Heuristic);
+    assert_eq!(heuristic.method, Method::Heuristic);
+    assert_eq!(heuristic.cost(), minimize(&table, 0, Form::Sop).cost());
+}
+
+#[test]
+fn bounds_petrick_on_dense_functions() {
+    // Dense enough that searching the cyclic core to the end would run for
+    // minutes
+    let table = random_table(10, 0x2545_f491);
+
+    let start = std::time::Instant::now();
+    for method in [Method::Auto, Method::Exact] {
+        let solution = minimize_with(&table, 0, Form::Sop, method);
+        assert_eq!(solution.method, Method::Heuristic);
+        assert_covers(&table, &solution);
+    }
+    // Unoptimised builds search about ten times slower
+    let limit = if cfg!(debug_assertions) { 40 } else { 10 };
+    assert!(start.elapsed() < std::time::Duration::from_secs(limit));
+}
+
+#[test]
+fn exact_falls_back_past_its_budget() {
+    // Thirteen dense inputs give about 9000 primes, more than the chart takes
+    let table = random_table(13, 0x2545_f491);
+    let solution = minimize_with(&table, 0, Form::Sop, Method::Exact);
+    assert_eq!(solution.method, Method::Heuristic);
+    assert_covers(&table, &solution);
+
+    let small = table_from(&["A", "B", "C"], &[0, 1, 2, 5, 6, 7], &[]);
+    assert_eq!(
+        minimize_with(&small, 0, Form::Sop, Method::Exact).method,
+        Method::Exact
+    );
+}
+
+#[test]
+fn exact_finds_the_minimum_up_to_eight_inputs() {
+    // Minimal costs confirmed by an exhaustive search, the heuristic misses
+    // each of them
+    for (variables, seed, terms, literals) in [(6, 4, 9, 30), (7, 4, 15, 69), (7, 1, 19, 90)] {
+        let table = random_table(variables, seed);
+        let solution = minimize(&table, 0, Form::Sop);
+        assert_eq!(solution.method, Method::Exact);
+        assert_eq!(solution.cost(), Cost { terms, literals });
+        assert_covers(&table, &solution);
+        for cover in &solution.solutions {
+            assert_eq!(Cost::of(cover, variables), solution.cost());
+        }
+
+        let heuristic = minimize_with(&table, 0, Form::Sop, Method::Heuristic);
+        assert!(heuristic.cost() > solution.cost());
+    }
+}
@@ -0,0 +1,328 @@
+use std::{
+    cmp::Reverse,
+    collections::{BTreeMap, BTreeSet},
+};
+
+use super::{Form, HEURISTIC_THRESHOLD, Implicant, Solution, minimize, select_cover};
+use crate::logic::{truth_table::TruthTable, variable::BitValue};
+
+/// Most outputs tagged QM handles, one bit of a `u32` tag each
+pub const MAX_SHARED_OUTPUTS: usize = u32::BITS as usize;
+
+/// A product term together with the outputs that use it
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SharedTerm {
+    pub implicant: Implicant,
+    pub outputs: Vec<usize>,
+}
+
+/// Cost of a multi-output sum-of-products laid out as a PLA
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PlaCost {
+    /// Distinct product terms, one PLA row each
+    pub products: usize,
+    /// Inputs to the AND plane, counted once per distinct product
+    pub literals: usize,
+    /// Inputs to the OR plane, one per product used by each output
+    pub connections: usize,
+}
+
+impl PlaCost {
+    pub fn of(covers: &[Vec<Implicant>], variables: usize) -> Self {
+        let distinct: BTreeSet<Implicant> = covers.iter().flatten().copied().collect();
+
+        Self {
+            products: distinct.len(),
+            literals: distinct.iter().map(|t| t.literal_count(variables)).sum(),
+            connections: covers.iter().map(Vec::len).sum(),
+        }
+    }
+}
+
+/// Result of minimizing every output of a truth table together
+#[derive(Clone, Debug, Default)]
+pub struct MultiSolution {
+    pub variables: usize,
+
+    /// Every distinct product term in the result
+    pub terms: Vec<SharedTerm>,
+    /// The cover chosen for each output, indexed like `TruthTable::outputs`
+    pub covers: Vec<Vec<Implicant>>,
+    /// Each output minimized on its own, used as the baseline for savings
+    pub separate: Vec<Solution>,
+}
+
+impl MultiSolution {
+    pub fn cost(&self) -> PlaCost {
+        PlaCost::of(&self.covers, self.variables)
+    }
+
+    pub fn separate_cost(&self) -> PlaCost {
+        separate_cost(&self.separate, self.variables)
+    }
+
+    /// How much the shared result saves over minimizing each output separately
+    pub fn savings(&self) -> PlaCost {
+        let shared = self.cost();
+        let separate = self.separate_cost();
+
+        PlaCost {
+            products: separate.products.saturating_sub(shared.products),
+            literals: separate.literals.saturating_sub(shared.literals),
+            connections: separate.connections.saturating_sub(shared.connections),
+        }
+    }
+}
+
+/// Minimizes all outputs of a truth table as one multi-output sum-of-products
+///
+/// Multi-output primes are found with tagged Quine–McCluskey, where every term
+/// carries the set of outputs it may belong to. Essential terms are picked
+/// first, the rest are chosen greedily by how many uncovered output cells they
+/// reach, and each output then takes a minimum cover from the selected terms.
+/// If that ends up costlier than the separate minimizations, the separate
+/// covers are kept instead. Tagged QM is exact and only attempted up to
+/// [`HEURISTIC_THRESHOLD`] inputs and [`MAX_SHARED_OUTPUTS`] outputs; larger
+/// tables keep the separate covers.
+///
+/// ### Parameters
+/// - `table`: The truth table holding every output
+pub fn minimize_shared(table: &TruthTable) -> MultiSolution {
+    let variables = table.inputs.len();
+    let outputs = table.outputs.len();
+
+    let separate: Vec<Solution> = (0..outputs)
+        .map(|o| minimize(table, o, Form::Sop))
+        .collect();
+
+    let shared = (variables <= HEURISTIC_THRESHOLD && outputs <= MAX_SHARED_OUTPUTS)
+        .then(|| select_shared(&tagged_primes(table), &separate, variables))
+        .filter(|covers| PlaCost::of(covers, variables) <= separate_cost(&separate, variables));
+
+    let covers = shared.unwrap_or_else(|| separate.iter().map(|s| s.cover.clone()).collect());
+
+    let mut terms: BTreeMap<Implicant, Vec<usize>> = BTreeMap::new();
+    for (o, cover) in covers.iter().enumerate() {
+        for term in cover {
+            terms.entry(*term).or_default().push(o);
+        }
+    }
+
+    MultiSolution {
+        variables,
+        terms: terms
+            .into_iter()
+            .map(|(implicant, outputs)| SharedTerm { implicant, outputs })
+            .collect(),
+        covers,
+        separate,
+    }
+}
+
+fn separate_cost(separate: &[Solution], variables: usize) -> PlaCost {
+    let covers: Vec<Vec<Implicant>> = separate.iter().map(|s| s.cover.clone()).collect();
+    PlaCost::of(&covers, variables)
+}
+
+/// Finds the multi-output prime implicants of the table
+///
+/// Each term is tagged with a bitmask of the outputs that are `One` or
+/// `DontCare` on all of its minterms. A merge keeps the intersection of both
+/// tags and only retires a parent whose tag survives unchanged. Tags are then
+/// trimmed to outputs the term actually helps cover.
+fn tagged_primes(table: &TruthTable) -> BTreeMap<Implicant, u32> {
+    let mut current: BTreeMap<Implicant, u32> = BTreeMap::new();
+    for (i, row) in table.rows.iter().enumerate() {
+        let tag = row
+            .outputs
+            .iter()
+            .enumerate()
+            .filter(|(_, v)| **v != BitValue::Zero)
+            .fold(0u32, |tag, (o, _)| tag | 1 << o);
+
+        if tag != 0 {
+            current.insert(Implicant::from_minterm(i as u32), tag);
+        }
+    }
+
+    let mut primes = BTreeMap::new();
+    while !current.is_empty() {
+        let mut groups: BTreeMap<(u32, u32), Vec<(Implicant, u32)>> = BTreeMap::new();
+        for (term, tag) in &current {
+            groups
+                .entry((term.mask, term.value.count_ones()))
+                .or_default()
+                .push((*term, *tag));
+        }
+
+        let mut retired = BTreeSet::new();
+        let mut next = BTreeMap::new();
+        for (&(mask, ones), terms) in &groups {
+            let Some(neighbours) = groups.get(&(mask, ones + 1)) else {
+                continue;
+            };
+
+            for (a, a_tag) in terms {
+                for (b, b_tag) in neighbours {
+                    let tag = a_tag & b_tag;
+                    if tag == 0 {
+                        continue;
+                    }
+
+                    let Some(c) = a.combine(b) else {
+                        continue;
+                    };
+
+                    if tag == *a_tag {
+                        retired.insert(*a);
+                    }
+                    if tag == *b_tag {
+                        retired.insert(*b);
+                    }
+                    next.insert(c, tag);
+                }
+            }
+        }
+
+        primes.extend(current.into_iter().filter(|(t, _)| !retired.contains(t)));
+        current = next;
+    }
+
+    primes
+        .into_iter()
+        .filter_map(|(term, tag)| {
+            let useful = (0..table.outputs.len())
+                .filter(|&o| tag & (1 << o) != 0)
+                .filter(|&o| {
+                    term.minterms()
+                        .iter()
+                        .any(|&m| table.rows[m as usize].outputs[o] == BitValue::One)
+                })
+                .fold(0u32, |t, o| t | 1 << o);
+
+            (useful != 0).then_some((term, useful))
+        })
+        .collect()
+}
+
+/// Chooses a set of shared terms, then a minimum cover per output from that set
+fn select_shared(
+    primes: &BTreeMap<Implicant, u32>,
+    separate: &[Solution],
+    variables: usize,
+) -> Vec<Vec<Implicant>> {
+    let covers_cell =
+        |term: &Implicant, tag: u32, (o, m): (usize, u32)| tag & (1 << o) != 0 && term.covers(m);
+
+    let mut uncovered: BTreeSet<(usize, u32)> = separate
+        .iter()
+        .enumerate()
+        .flat_map(|(o, s)| s.minterms.iter().map(move |&m| (o, m)))
+        .collect();
+
+    let mut selected: BTreeSet<Implicant> = BTreeSet::new();
+    for &cell in &uncovered {
+        let mut covering = primes.iter().filter(|(t, tag)| covers_cell(t, **tag, cell));
+        if let (Some((term, _)), None) = (covering.next(), covering.next()) {
+            selected.insert(*term);
+        }
+    }
+
+    uncovered.retain(|&cell| !selected.iter().any(|t| covers_cell(t, primes[t], cell)));
+
+    while !uncovered.is_empty() {
+        let Some((best, tag)) = primes
+            .iter()
+            .filter(|(t, _)| !selected.contains(t))
+            .max_by_key(|(t, tag)| {
+                let hits = uncovered
+                    .iter()
+                    .filter(|&&cell| covers_cell(t, **tag, cell))
+                    .count();
+                (hits, Reverse(t.literal_count(variables)))
+            })
+        else {
+            break;
+        };
+
+        uncovered.retain(|&cell| !covers_cell(best, *tag, cell));
+        selected.insert(*best);
+    }
+
+    separate
+        .iter()
+        .enumerate()
+        .map(|(o, s)| {
+            let candidates: Vec<Implicant> = selected
+                .iter()
+                .copied()
+                .filter(|t| primes[t] & (1 << o) != 0)
+                .collect();
+
+            select_cover(&candidates, &[], &s.minterms, variables)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+fn table_from(variables: &[&str], outputs: &[&[u32]]) -> TruthTable {
+    let names: Vec<String> = (0..outputs.len()).map(|o| format!("F{o}")).collect();
+    let mut table = TruthTable::new(variables, &names);
+    for row in 0..table.rows.len() {
+        for (o, ones) in outputs.iter().enumerate() {
+            let value = if ones.contains(&(row as u32)) {
+                BitValue::One
+            } else {
+                BitValue::Zero
+            };
+            table.set(row, o, value);
+        }
+    }
+    table
+}
+
+#[test]
+fn shares_terms_between_outputs() {
+    // F0 = A'B'C' and F1 = Σm(0,2,3). Alone F1 = A'C' + A'B, but reusing
+    // F0's term as F1 = A'B'C' + A'B saves a PLA row.
+    let table = table_from(&["A", "B", "C"], &[&[0], &[0, 2, 3]]);
+    let solution = minimize_shared(&table);
+
+    for (o, cover) in solution.covers.iter().enumerate() {
+        for &m in &solution.separate[o].minterms {
+            assert!(cover.iter().any(|t| t.covers(m)));
+        }
+    }
+
+    assert_eq!(solution.terms.len(), 2);
+    assert!(
+        solution
+            .terms
+            .iter()
+            .any(|t| t.implicant.pattern(3) == "000" && t.outputs == vec![0, 1])
+    );
+    assert_eq!(solution.separate_cost().products, 3);
+    assert_eq!(solution.savings().products, 1);
+}
+
+#[test]
+fn never_worse_than_separate() {
+    // F0 = Σm(1,3,5,7) = C, F1 = Σm(3,7) = BC
+    let table = table_from(&["A", "B", "C"], &[&[1, 3, 5, 7], &[3, 7]]);
+    let solution = minimize_shared(&table);
+
+    assert!(solution.cost() <= solution.separate_cost());
+    assert_eq!(solution.savings(), PlaCost::default());
+}
+
+#[test]
+fn keeps_separate_covers_past_the_output_limit() {
+    let ones: Vec<&[u32]> = (0..=MAX_SHARED_OUTPUTS)
+        .map(|o| if o % 2 == 0 { &[1, 3][..] } else { &[2][..] })
+        .collect();
+    let table = table_from(&["A", "B"], &ones);
+    let solution = minimize_shared(&table);
+
+    assert_eq!(solution.covers.len(), MAX_SHARED_OUTPUTS + 1);
+    assert_eq!(solution.cost(), solution.separate_cost());
+}
@@ -0,0 +1,274 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use super::{Form, Implicant, Method, Solution, essential_primes};
+
+/// Most inputs [`Solution::tableau`] walks through, past that the merging
+/// rounds hold too many terms to show
+pub const MAX_INPUTS: usize = 10;
+
+/// A term in one column of the tableau
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Entry {
+    pub implicant: Implicant,
+    /// Combined with a neighbour into the next round, the textbook check mark
+    pub merged: bool,
+}
+
+/// Terms of one round sharing the same number of 1 bits
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Group {
+    pub ones: u32,
+    pub terms: Vec<Entry>,
+}
+
+/// One column of the merging stage, groups in ascending popcount
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Round {
+    pub groups: Vec<Group>,
+}
+
+impl Round {
+    pub fn entries(&self) -> impl Iterator<Item = &Entry> {
+        self.groups.iter().flat_map(|g| &g.terms)
+    }
+}
+
+/// A step of the walkthrough
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Stage {
+    /// Round 0 holds the minterms, later rounds the merged terms
+    Round(usize),
+    Primes,
+    /// The prime implicant chart and the cover picked from it
+    Chart,
+}
+
+/// Every intermediate result of Quine–McCluskey for one function
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Tableau {
+    pub form: Form,
+    pub variables: usize,
+    pub minterms: Vec<u32>,
+    pub dont_cares: Vec<u32>,
+    pub rounds: Vec<Round>,
+    /// Unmerged terms covering at least one minterm, the chart rows
+    pub primes: Vec<Implicant>,
+    pub essential: Vec<Implicant>,
+    /// Chart columns with a single cross, which make their row essential
+    pub essential_columns: Vec<u32>,
+    /// The cover of the solution the tableau was built from
+    pub cover: Vec<Implicant>,
+    /// How that cover was found, the heuristic when the chart was too large
+    /// to solve exactly
+    pub method: Method,
+}
+
+impl Tableau {
+    /// Reruns Quine–McCluskey on the function of `solution`, keeping what each
+    /// stage produced, and ends on the solution's own cover
+    fn new(solution: &Solution) -> Self {
+        let Solution {
+            form,
+            variables,
+            ref minterms,
+            ref dont_cares,
+            ..
+        } = *solution;
+        let rounds = merge_rounds(minterms, dont_cares);
+        let primes = unmerged(&rounds, minterms);
+        let essential = essential_primes(&primes, minterms);
+        let essential_columns = minterms
+            .iter()
+            .copied()
+            .filter(|&m| primes.iter().filter(|p| p.covers(m)).count() == 1)
+            .collect();
+
+        Self {
+            form,
+            variables,
+            minterms: minterms.to_vec(),
+            dont_cares: dont_cares.to_vec(),
+            rounds,
+            primes,
+            essential,
+            essential_columns,
+            cover: solution.cover.clone(),
+            method: solution.method,
+        }
+    }
+
+    /// The steps in walkthrough order
+    pub fn stages(&self) -> Vec<Stage> {
+        (0..self.rounds.len())
+            .map(Stage::Round)
+            .chain([Stage::Primes, Stage::Chart])
+            .collect()
+    }
+
+    /// Whether this tableau was built from the same function as `solution`
+    pub fn matches(&self, solution: &Solution) -> bool {
+        self.form == solution.form
+            && self.variables == solution.variables
+            && self.minterms == solution.minterms
+            && self.dont_cares == solution.dont_cares
+            && self.cover == solution.cover
+    }
+}
+
+impl Solution {
+    /// Reruns Quine–McCluskey on this function, recording every stage
+    ///
+    /// The chart ends on [`Solution::cover`], so the walkthrough arrives at
+    /// the same answer as the other views, heuristic or not. Returns `None`
+    /// past [`MAX_INPUTS`].
+    ///
+    /// ### Examples
+    /// ```
+    /// # use karny::logic::solver::{Form, Solution};
+    /// let solution = Solution { form: Form::Sop, variables: 2, minterms: vec![1, 3], ..Default::default() };
+    /// let tableau = solution.tableau().unwrap();
+    /// assert_eq!(tableau.rounds.len(), 2);
+    /// assert_eq!(tableau.primes.len(), 1);
+    /// ```
+    pub fn tableau(&self) -> Option<Tableau> {
+        (self.variables <= MAX_INPUTS).then(|| Tableau::new(self))
+    }
+}
+
+/// Merges terms round by round until nothing combines
+///
+/// Terms are only compared with those one popcount higher and with the same
+/// eliminated variables, since no other pair can differ in a single literal.
+pub fn merge_rounds(minterms: &[u32], dont_cares: &[u32]) -> Vec<Round> {
+    let mut current: BTreeSet<Implicant> = minterms
+        .iter()
+        .chain(dont_cares)
+        .copied()
+        .map(Implicant::from_minterm)
+        .collect();
+
+    let mut rounds = Vec::new();
+    while !current.is_empty() {
+        let mut buckets: BTreeMap<(u32, u32), Vec<Implicant>> = BTreeMap::new();
+        for term in &current {
+            buckets
+                .entry((term.value.count_ones(), term.mask))
+                .or_default()
+                .push(*term);
+        }
+
+        let mut merged = BTreeSet::new();
+        let mut next = BTreeSet::new();
+        for (&(ones, mask), terms) in &buckets {
+            let Some(neighbours) = buckets.get(&(ones + 1, mask)) else {
+                continue;
+            };
+
+            for a in terms {
+                for b in neighbours {
+                    if let Some(c) = a.combine(b) {
+                        merged.insert(*a);
+                        merged.insert(*b);
+                        next.insert(c);
+                    }
+                }
+            }
+        }
+
+        let mut groups: BTreeMap<u32, Vec<Entry>> = BTreeMap::new();
+        for ((ones, _), terms) in buckets {
+            groups
+                .entry(ones)
+                .or_default()
+                .extend(terms.into_iter().map(|t| Entry {
+                    implicant: t,
+                    merged: merged.contains(&t),
+                }));
+        }
+        let groups = groups
+            .into_iter()
+            .map(|(ones, mut terms)| {
+                terms.sort_by_cached_key(|e| e.implicant.minterms());
+                Group { ones, terms }
+            })
+            .collect();
+
+        rounds.push(Round { groups });
+        current = next;
+    }
+    rounds
+}
+
+/// Terms no round merged, minus those made up entirely of don't cares
+pub(super) fn unmerged(rounds: &[Round], minterms: &[u32]) -> Vec<Implicant> {
+    let primes: BTreeSet<Implicant> = rounds
+        .iter()
+        .flat_map(Round::entries)
+        .filter(|e| !e.merged)
+        .map(|e| e.implicant)
+        .filter(|p| minterms.iter().any(|&m| p.covers(m)))
+        .collect();
+    primes.into_iter().collect()
+}
+
+#[test]
+fn records_every_stage() {
+    // F(A,B,C,D) = Σm(4,8,10,11,12,15) + d(9,14)
+    let table = super::table_from(&["A", "B", "C", "D"], &[4, 8, 10, 11, 12, 15], &[9, 14]);
+    let solution = super::minimize(&table, 0, Form::Sop);
+    let tableau = solution.tableau().unwrap();
+
+    // Minterms, pairs and quads, nothing merges past the quads
+    assert_eq!(tableau.rounds.len(), 3);
+    let ones: Vec<u32> = tableau.rounds[0].groups.iter().map(|g| g.ones).collect();
+    assert_eq!(ones, vec![1, 2, 3, 4]);
+    assert!(tableau.rounds[0].entries().all(|e| e.merged));
+
+    // B C' D' (4, 12) is the only pair left unchecked
+    let unchecked: Vec<Implicant> = tableau.rounds[1]
+        .entries()
+        .filter(|e| !e.merged)
+        .map(|e| e.implicant)
+        .collect();
+    assert_eq!(
+        unchecked,
+        vec![Implicant {
+            value: 0b0100,
+            mask: 0b1000
+        }]
+    );
+
+    assert_eq!(
+        tableau.primes,
+        super::prime_implicants(&[4, 8, 10, 11, 12, 15], &[9, 14])
+    );
+    assert_eq!(tableau.essential_columns, vec![4, 15]);
+    assert_eq!(tableau.essential.len(), 2);
+    assert_eq!(tableau.cover.len(), 3);
+    assert_eq!(
+        tableau.stages(),
+        vec![
+            Stage::Round(0),
+            Stage::Round(1),
+            Stage::Round(2),
+            Stage::Primes,
+            Stage::Chart
+        ]
+    );
+}
+
+#[test]
+fn ends_on_the_solution_cover() {
+    let table = super::table_from(&["A", "B", "C"], &[0, 1, 2, 5, 6, 7], &[]);
+    let solution = super::minimize_with(&table, 0, Form::Sop, Method::Heuristic);
+    let tableau = solution.tableau().unwrap();
+    assert_eq!(tableau.cover, solution.cover);
+    assert_eq!(tableau.method, Method::Heuristic);
+    assert!(tableau.matches(&solution));
+
+    let large = Solution {
+        variables: MAX_INPUTS + 1,
+        ..Default::default()
+    };
+    assert!(large.tableau().is_none());
+}
@@ -1,56 +1,336 @@
-use std::fmt;
+use std::{collections::BTreeSet, fmt};
 
-use crate::logic::variable::BitValue;
+use crate::logic::{
+    gray::{self, SizeError},
+    variable::BitValue,
+};
 
-#[derive(Clone, Debug, Default)]
+/// Returned when an input index is past the last input of a table
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NoSuchInput(pub usize);
+
+impl fmt::Display for NoSuchInput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "input {} does not exist", self.0)
+    }
+}
+
+impl std::error::Error for NoSuchInput {}
+
+/// Why [`TruthTable::insert_input`] refused an input
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InsertInputError {
+    /// The table would exceed [`gray::MAX_VARIABLES`] inputs
+    Size(SizeError),
+    /// The index is past the end of the inputs
+    NoSuchInput(NoSuchInput),
+}
+
+impl From<SizeError> for InsertInputError {
+    fn from(err: SizeError) -> Self {
+        InsertInputError::Size(err)
+    }
+}
+
+impl From<NoSuchInput> for InsertInputError {
+    fn from(err: NoSuchInput) -> Self {
+        InsertInputError::NoSuchInput(err)
+    }
+}
+
+impl fmt::Display for InsertInputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InsertInputError::Size(err) => write!(f, "{err}"),
+            InsertInputError::NoSuchInput(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl std::error::Error for InsertInputError {}
+
+/// Why [`TruthTable::check_name`] refused a variable name
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum NameError {
+    Empty,
+    /// Another input or output already has the name
+    Taken(String),
+}
+
+impl fmt::Display for NameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NameError::Empty => write!(f, "the name is empty"),
+            NameError::Taken(name) => write!(f, "{name} is already a variable"),
+        }
+    }
+}
+
+impl std::error::Error for NameError {}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct TruthTable {
     pub inputs: Vec<String>,
     pub outputs: Vec<String>,
 
     pub rows: Vec<TruthRow>,
+
+    /// `(row, output)` cells whose value was dropped because two merged
+    /// rows disagreed, cleared once the cell is edited
+    pub conflicts: BTreeSet<(usize, usize)>,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct TruthRow {
     pub inputs: Vec<BitValue>,
     pub outputs: Vec<BitValue>,
 }
 
+impl Default for TruthTable {
+    /// A table without variables still has its single empty row
+    fn default() -> Self {
+        Self::new::<&str, &str>(&[], &[])
+    }
+}
+
 impl TruthTable {
+    /// Creates a table with every output set to `DontCare`
+    ///
+    /// Panics if there are more inputs than [`gray::MAX_VARIABLES`], use
+    /// [`TruthTable::try_new`] when the input count is not known to be valid.
     pub fn new<I, O>(inputs: &[I], outputs: &[O]) -> Self
     where
         I: Clone + Into<String>,
         O: Clone + Into<String>,
     {
+        Self::try_new(inputs, outputs).unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    pub fn try_new<I, O>(inputs: &[I], outputs: &[O]) -> Result<Self, SizeError>
+    where
+        I: Clone + Into<String>,
+        O: Clone + Into<String>,
+    {
+        gray::check_size(inputs.len())?;
+
         let inputs: Vec<String> = inputs.iter().cloned().map(Into::into).collect();
         let outputs: Vec<String> = outputs.iter().cloned().map(Into::into).collect();
 
-        let num_rows = 1 << inputs.len();
+        let num_rows = 1usize << inputs.len();
         let rows = (0..num_rows)
-            .map(|i| {
-                let bits = (0..inputs.len())
-                    .map(|b| {
-                        if (i >> (inputs.len() - 1 - b)) & 1 == 1 {
-                            BitValue::One
-                        } else {
-                            BitValue::Zero
+            .map(|i| TruthRow {
+                inputs: input_bits(i, inputs.len()),
+                outputs: vec![BitValue::DontCare; outputs.len()],
+            })
+            .collect();
+
+        Ok(Self {
+            inputs,
+            outputs,
+            rows,
+            conflicts: BTreeSet::new(),
+        })
+    }
+
+    /// Checks that `name` is not blank and not used by any input or output
+    /// other than `current`, the variable being renamed
+    ///
+    /// Outputs are looked up by name elsewhere, so two variables sharing one
+    /// would overwrite each other's settings.
+    pub fn check_name(&self, name: &str, current: Option<&str>) -> Result<(), NameError> {
+        if name.trim().is_empty() {
+            return Err(NameError::Empty);
+        }
+        if current != Some(name) && self.inputs.iter().chain(&self.outputs).any(|v| v == name) {
+            return Err(NameError::Taken(name.to_string()));
+        }
+        Ok(())
+    }
+
+    /// Inserts an input at `index`, copying every output into both the rows
+    /// where the new input is 0 and where it is 1
+    ///
+    /// ### Returns
+    /// An error if `index` is past the last input, or if the table would
+    /// exceed [`gray::MAX_VARIABLES`] inputs
+    pub fn insert_input(
+        &mut self,
+        index: usize,
+        name: impl Into<String>,
+    ) -> Result<(), InsertInputError> {
+        if index > self.inputs.len() {
+            return Err(NoSuchInput(index).into());
+        }
+        let n = self.inputs.len() + 1;
+        gray::check_size(n)?;
+
+        // Weight of the new input in the widened row index
+        let shift = n - 1 - index;
+        let old_row = |row: usize| (row >> (shift + 1) << shift) | (row & ((1 << shift) - 1));
+
+        self.inputs.insert(index, name.into());
+        self.rows = (0..1usize << n)
+            .map(|row| TruthRow {
+                inputs: input_bits(row, n),
+                outputs: self.rows[old_row(row)].outputs.clone(),
+            })
+            .collect();
+        self.conflicts = self
+            .conflicts
+            .iter()
+            .flat_map(|&(row, output)| {
+                let high = row >> shift << (shift + 1);
+                let base = high | (row & ((1 << shift) - 1));
+                [(base, output), (base | (1 << shift), output)]
+            })
+            .collect();
+
+        Ok(())
+    }
+
+    /// Removes the input at `index`, merging each pair of rows that only
+    /// differed in that input
+    ///
+    /// A don't care yields to the other half. When both halves hold
+    /// different defined values the merged cell becomes a don't care and
+    /// is recorded in [`TruthTable::conflicts`].
+    ///
+    /// ### Returns
+    /// The number of new conflicts, or an error if there is no input at `index`
+    pub fn remove_input(&mut self, index: usize) -> Result<usize, NoSuchInput> {
+        if index >= self.inputs.len() {
+            return Err(NoSuchInput(index));
+        }
+        let n = self.inputs.len() - 1;
+        let shift = n - index;
+        let split = |row: usize| {
+            let base = (row >> shift << (shift + 1)) | (row & ((1 << shift) - 1));
+            (base, base | (1 << shift))
+        };
+
+        let mut conflicts = BTreeSet::new();
+        let mut new_conflicts = 0;
+        let rows = (0..1usize << n)
+            .map(|row| {
+                let (low, high) = split(row);
+                let outputs = (0..self.outputs.len())
+                    .map(|output| {
+                        let (a, b) = (
+                            self.rows[low].outputs[output],
+                            self.rows[high].outputs[output],
+                        );
+                        let was_conflict = self.conflicts.contains(&(low, output))
+                            || self.conflicts.contains(&(high, output));
+
+                        match (a, b) {
+                            (a, b) if a == b => {
+                                if was_conflict {
+                                    conflicts.insert((row, output));
+                                }
+                                a
+                            }
+                            (BitValue::DontCare, v) | (v, BitValue::DontCare) => {
+                                if was_conflict {
+                                    conflicts.insert((row, output));
+                                }
+                                v
+                            }
+                            _ => {
+                                conflicts.insert((row, output));
+                                new_conflicts += 1;
+                                BitValue::DontCare
+                            }
                         }
                     })
                     .collect();
+
                 TruthRow {
-                    inputs: bits,
-                    outputs: vec![BitValue::DontCare; outputs.len()],
+                    inputs: input_bits(row, n),
+                    outputs,
                 }
             })
             .collect();
 
-        Self {
-            inputs,
-            outputs,
-            rows,
+        self.inputs.remove(index);
+        self.rows = rows;
+        self.conflicts = conflicts;
+        Ok(new_conflicts)
+    }
+
+    /// Reorders the inputs so that input `i` becomes the old input `order[i]`,
+    /// moving rows so every output still describes the same function
+    pub fn permute_inputs(&mut self, order: &[usize]) {
+        let n = self.inputs.len();
+        assert_eq!(order.len(), n, "permutation must cover every input");
+
+        let old_row = |row: usize| {
+            order.iter().enumerate().fold(0, |acc, (i, &from)| {
+                let bit = (row >> (n - 1 - i)) & 1;
+                acc | (bit << (n - 1 - from))
+            })
+        };
+        let new_of_old: Vec<usize> = {
+            let mut map = vec![0; self.rows.len()];
+            for row in 0..self.rows.len() {
+                map[old_row(row)] = row;
+            }
+            map
+        };
+
+        self.inputs = order.iter().map(|&i| self.inputs[i].clone()).collect();
+        self.rows = (0..self.rows.len())
+            .map(|row| TruthRow {
+                inputs: input_bits(row, n),
+                outputs: self.rows[old_row(row)].outputs.clone(),
+            })
+            .collect();
+        self.conflicts = self
+            .conflicts
+            .iter()
+            .map(|&(row, output)| (new_of_old[row], output))
+            .collect();
+    }
+
+    /// Appends an output column filled with don't cares
+    pub fn add_output(&mut self, name: impl Into<String>) {
+        self.outputs.push(name.into());
+        for row in &mut self.rows {
+            row.outputs.push(BitValue::DontCare);
         }
     }
 
+    pub fn remove_output(&mut self, index: usize) {
+        self.outputs.remove(index);
+        for row in &mut self.rows {
+            row.outputs.remove(index);
+        }
+        self.conflicts = self
+            .conflicts
+            .iter()
+            .filter(|&&(_, output)| output != index)
+            .map(|&(row, output)| (row, if output > index { output - 1 } else { output }))
+            .collect();
+    }
+
+    /// Reorders the outputs so that output `i` becomes the old output `order[i]`
+    pub fn permute_outputs(&mut self, order: &[usize]) {
+        assert_eq!(
+            order.len(),
+            self.outputs.len(),
+            "permutation must cover every output"
+        );
+
+        self.outputs = order.iter().map(|&i| self.outputs[i].clone()).collect();
+        for row in &mut self.rows {
+            row.outputs = order.iter().map(|&i| row.outputs[i]).collect();
+        }
+        self.conflicts = self
+            .conflicts
+            .iter()
+            .map(|&(row, output)| (row, order.iter().position(|&i| i == output).unwrap()))
+            .collect();
+    }
+
     fn get_output_cell(&mut self, row: usize, output_index: usize) -> Option<&mut BitValue> {
         self.rows
             .get_mut(row)
@@ -60,14 +340,43 @@ impl TruthTable {
     pub fn toggle(&mut self, row: usize, output_index: usize) {
         if let Some(cell) = self.get_output_cell(row, output_index) {
             cell.toggle();
+            self.conflicts.remove(&(row, output_index));
         }
     }
 
     pub fn set(&mut self, row: usize, output_index: usize, value: BitValue) {
         if let Some(cell) = self.get_output_cell(row, output_index) {
             cell.set(value);
+            self.conflicts.remove(&(row, output_index));
         }
     }
+
+    /// Collects the row indices (minterm numbers) whose output equals `value`
+    /// ### Parameters
+    /// - `output_index`: Index of the output column to inspect
+    /// - `value`: The cell value to match
+    pub fn terms(&self, output_index: usize, value: BitValue) -> Vec<u32> {
+        self.rows
+            .iter()
+            .enumerate()
+            .filter(|(_, row)| row.outputs.get(output_index) == Some(&value))
+            .map(|(i, _)| i as u32)
+            .collect()
+    }
+}
+
+/// Input values of row `row` in a table with `n` inputs, first input in the
+/// most significant bit
+fn input_bits(row: usize, n: usize) -> Vec<BitValue> {
+    (0..n)
+        .map(|b| {
+            if (row >> (n - 1 - b)) & 1 == 1 {
+                BitValue::One
+            } else {
+                BitValue::Zero
+            }
+        })
+        .collect()
 }
 
 impl fmt::Display for TruthTable {
@@ -137,3 +446,113 @@ impl fmt::Display for TruthTable {
         Ok(())
     }
 }
+
+#[cfg(test)]
+fn outputs(table: &TruthTable, output: usize) -> Vec<BitValue> {
+    table.rows.iter().map(|row| row.outputs[output]).collect()
+}
+
+#[test]
+fn inserted_inputs_duplicate_outputs() {
+    use BitValue::*;
+
+    let mut table = TruthTable::default();
+    table.add_output("F");
+    table.insert_input(0, "A").unwrap();
+    table.set(0, 0, One);
+    table.set(1, 0, Zero);
+
+    // F = A' must not depend on the new input wherever it lands
+    table.insert_input(0, "B").unwrap();
+    assert_eq!(table.inputs, vec!["B", "A"]);
+    assert_eq!(outputs(&table, 0), vec![One, Zero, One, Zero]);
+
+    table.insert_input(2, "C").unwrap();
+    assert_eq!(table.inputs, vec!["B", "A", "C"]);
+    assert_eq!(
+        outputs(&table, 0),
+        vec![One, One, Zero, Zero, One, One, Zero, Zero]
+    );
+    assert_eq!(table.rows[5].inputs, vec![One, Zero, One]);
+
+    assert_eq!(
+        table.insert_input(4, "D"),
+        Err(InsertInputError::NoSuchInput(NoSuchInput(4)))
+    );
+    assert_eq!(table.inputs.len(), 3);
+
+    let mut full = TruthTable::new(&["A"; gray::MAX_VARIABLES], &["F"]);
+    assert_eq!(
+        full.insert_input(0, "Q"),
+        Err(InsertInputError::Size(SizeError {
+            variables: gray::MAX_VARIABLES + 1
+        }))
+    );
+}
+
+#[test]
+fn removed_inputs_merge_rows_and_flag_conflicts() {
+    use BitValue::*;
+
+    let mut table = TruthTable::new(&["A", "B"], &["F", "G"]);
+    // F = A (disagrees across B only where noted), G = B
+    for (row, f, g) in [
+        (0, Zero, Zero),
+        (1, DontCare, One),
+        (2, One, Zero),
+        (3, One, One),
+    ] {
+        table.set(row, 0, f);
+        table.set(row, 1, g);
+    }
+
+    // Dropping B: F merges cleanly, G conflicts in both rows
+    assert_eq!(table.remove_input(1), Ok(2));
+    assert_eq!(table.inputs, vec!["A"]);
+    assert_eq!(table.remove_input(1), Err(NoSuchInput(1)));
+    assert_eq!(outputs(&table, 0), vec![Zero, One]);
+    assert_eq!(outputs(&table, 1), vec![DontCare, DontCare]);
+    assert_eq!(table.conflicts, BTreeSet::from([(0, 1), (1, 1)]));
+
+    // Conflicts follow their cells and clear once edited
+    table.remove_output(0);
+    assert_eq!(table.conflicts, BTreeSet::from([(0, 0), (1, 0)]));
+    table.toggle(1, 0);
+    assert_eq!(table.conflicts, BTreeSet::from([(0, 0)]));
+}
+
+#[test]
+fn permuting_inputs_keeps_functions() {
+    use BitValue::*;
+
+    let mut table = TruthTable::new(&["A", "B", "C"], &["F"]);
+    // F = A B'
+    for row in 0..8 {
+        table.set(row, 0, if row >> 1 == 0b10 { One } else { Zero });
+    }
+
+    table.permute_inputs(&[2, 0, 1]);
+    assert_eq!(table.inputs, vec!["C", "A", "B"]);
+    for row in &table.rows {
+        let expected = row.inputs[1] == One && row.inputs[2] == Zero;
+        assert_eq!(row.outputs[0] == One, expected);
+    }
+
+    table.add_output("G");
+    table.set(0, 1, One);
+    table.permute_outputs(&[1, 0]);
+    assert_eq!(table.outputs, vec!["G", "F"]);
+    assert_eq!(table.rows[0].outputs, vec![One, Zero]);
+}
+
+#[test]
+fn checks_names() {
+    let table = TruthTable::new(&["A", "B"], &["F"]);
+    assert_eq!(table.check_name("C", None), Ok(()));
+    assert_eq!(table.check_name("A", Some("A")), Ok(()));
+    assert_eq!(table.check_name(" ", Some("A")), Err(NameError::Empty));
+    assert_eq!(
+        table.check_name("F", Some("A")),
+        Err(NameError::Taken("F".to_string()))
+    );
+}
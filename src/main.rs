@@ -1,7 +1,41 @@
+use std::process::ExitCode;
+
+#[cfg(any(feature = "gui", feature = "cli"))]
+use karny::logic;
+
+#[cfg(feature = "gui")]
 mod app;
-mod logic;
+#[cfg(feature = "cli")]
+mod cli;
+#[cfg(feature = "gui")]
+mod project;
+#[cfg(feature = "gui")]
 mod ui;
 
-fn main() -> Result<(), eframe::Error> {
-    app::app()
+#[cfg(feature = "gui")]
+fn main() -> ExitCode {
+    // Any argument switches to the headless interface
+    #[cfg(feature = "cli")]
+    if std::env::args_os().len() > 1 {
+        return cli::run();
+    }
+
+    match app::app() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("{err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(all(feature = "cli", not(feature = "gui")))]
+fn main() -> ExitCode {
+    cli::run()
+}
+
+#[cfg(not(any(feature = "gui", feature = "cli")))]
+fn main() -> ExitCode {
+    eprintln!("karny was built without the gui and cli features");
+    ExitCode::FAILURE
 }
@@ -0,0 +1,72 @@
+//! Forward migrations for project files
+//!
+//! Each step rewrites the raw TOML of one version into the next, so a file
+//! from any older version is upgraded step by step before it is
+//! deserialized. When the schema changes, bump
+//! [`super::CURRENT_VERSION`] and append a step here.
+
+use toml::{Table, Value};
+
+/// `STEPS[i]` upgrades a version `i + 1` document to version `i + 2`
+const STEPS: &[fn(&mut Table)] = &[row_count_to_axes];
+
+/// Upgrades `doc` from `version` to [`super::CURRENT_VERSION`]
+pub fn upgrade(doc: &mut Table, version: u32) {
+    for step in &STEPS[(version - 1) as usize..] {
+        step(doc);
+    }
+    doc.insert(
+        "version".to_string(),
+        Value::Integer(super::CURRENT_VERSION as i64),
+    );
+}
+
+/// Version 2 names the variables on each map axis, version 1 stored how
+/// many leading inputs went on the rows
+fn row_count_to_axes(doc: &mut Table) {
+    let inputs: Vec<Value> = doc
+        .get("inputs")
+        .and_then(Value::as_array)
+        .map(|inputs| {
+            inputs
+                .iter()
+                .filter_map(|i| i.get("name").cloned())
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let Some(outputs) = doc.get_mut("outputs").and_then(Value::as_array_mut) else {
+        return;
+    };
+    for output in outputs.iter_mut().filter_map(Value::as_table_mut) {
+        let Some(rows) = output.remove("map_rows").and_then(|v| v.as_integer()) else {
+            continue;
+        };
+
+        let rows = (rows.max(0) as usize).min(inputs.len());
+        let mut axes = Table::new();
+        axes.insert("rows".to_string(), Value::Array(inputs[..rows].to_vec()));
+        axes.insert("cols".to_string(), Value::Array(inputs[rows..].to_vec()));
+        output.insert("map".to_string(), Value::Table(axes));
+    }
+}
+
+#[test]
+fn upgrades_row_counts_to_axes() {
+    let mut doc: Table = r#"
+        version = 1
+        inputs = [{ name = "A", value = "x" }, { name = "B", value = "x" }, { name = "C", value = "x" }]
+        outputs = [{ name = "F", value = "x", map_rows = 1 }, { name = "G", value = "x" }]
+    "#
+    .parse()
+    .unwrap();
+    upgrade(&mut doc, 1);
+
+    let outputs = doc["outputs"].as_array().unwrap();
+    let map = outputs[0]["map"].as_table().unwrap();
+    assert_eq!(map["rows"].as_array().unwrap().len(), 1);
+    assert_eq!(map["cols"].as_array().unwrap()[0].as_str(), Some("B"));
+    assert!(!outputs[0].as_table().unwrap().contains_key("map_rows"));
+    assert!(outputs[1].get("map").is_none());
+    assert_eq!(doc["version"].as_integer(), Some(2));
+}
@@ -0,0 +1,411 @@
+mod migrate;
+
+use std::{fmt, fs, io, path::Path};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    app::AppState,
+    logic::{
+        gray::{self, SizeError},
+        truth_table::TruthTable,
+        variable::{BitValue, VariableKind},
+    },
+    ui::{
+        components::{map_view::AxisLayout, menubar::WindowState},
+        variable::VariableStore,
+    },
+};
+
+/// Schema version written by this build, see [`migrate`] for older files
+pub const CURRENT_VERSION: u32 = 2;
+
+/// File extension used by the open and save dialogs
+pub const EXTENSION: &str = "karny.toml";
+
+#[derive(Debug)]
+pub enum ProjectError {
+    Io(io::Error),
+    Parse(toml::de::Error),
+    Serialize(toml::ser::Error),
+    /// The file has no integer `version` field
+    MissingVersion,
+    /// The file was written by a newer build
+    UnsupportedVersion(i64),
+    Size(SizeError),
+    /// The file parsed but its contents don't describe a valid project
+    Invalid(String),
+}
+
+impl From<io::Error> for ProjectError {
+    fn from(err: io::Error) -> Self {
+        ProjectError::Io(err)
+    }
+}
+
+impl From<toml::de::Error> for ProjectError {
+    fn from(err: toml::de::Error) -> Self {
+        ProjectError::Parse(err)
+    }
+}
+
+impl From<toml::ser::Error> for ProjectError {
+    fn from(err: toml::ser::Error) -> Self {
+        ProjectError::Serialize(err)
+    }
+}
+
+impl From<SizeError> for ProjectError {
+    fn from(err: SizeError) -> Self {
+        ProjectError::Size(err)
+    }
+}
+
+impl fmt::Display for ProjectError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProjectError::Io(err) => write!(f, "{err}"),
+            ProjectError::Parse(err) => write!(f, "invalid project file: {err}"),
+            ProjectError::Serialize(err) => write!(f, "could not write project: {err}"),
+            ProjectError::MissingVersion => write!(f, "project file has no version"),
+            ProjectError::UnsupportedVersion(version) => write!(
+                f,
+                "project version {version} is newer than the supported version {CURRENT_VERSION}"
+            ),
+            ProjectError::Size(err) => write!(f, "{err}"),
+            ProjectError::Invalid(msg) => write!(f, "invalid project file: {msg}"),
+        }
+    }
+}
+
+impl std::error::Error for ProjectError {}
+
+/// On-disk project, stored as TOML
+///
+/// The file mirrors the app state with plain types so the format stays
+/// stable while the in-memory types change. The truth table is written one
+/// row per line so edits show up as single-line diffs.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ProjectFile {
+    pub version: u32,
+
+    /// One entry per row in row order, input bits then output values,
+    /// e.g. `"01|1x"`
+    #[serde(default)]
+    pub rows: Vec<String>,
+
+    #[serde(default)]
+    pub window: WindowLayout,
+    #[serde(default)]
+    pub inputs: Vec<InputEntry>,
+    #[serde(default)]
+    pub outputs: Vec<OutputEntry>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WindowLayout {
+    pub table_view: bool,
+    pub map_view: bool,
+    pub expression_view: bool,
+    pub history_view: bool,
+    pub tableau_view: bool,
+}
+
+impl Default for WindowLayout {
+    fn default() -> Self {
+        let window = WindowState::default();
+        Self {
+            table_view: window.table_view,
+            map_view: window.map_view,
+            expression_view: window.expression_view,
+            history_view: window.history_view,
+            tableau_view: window.tableau_view,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct InputEntry {
+    pub name: String,
+    /// `'0'`, `'1'` or `'x'`
+    pub value: char,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct OutputEntry {
+    pub name: String,
+    /// `'0'`, `'1'` or `'x'`
+    pub value: char,
+    /// Input names on each axis of the K-map, automatic when absent
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub map: Option<MapAxes>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MapAxes {
+    /// Variables selecting a sub-map, only written for stacked maps
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub maps: Vec<String>,
+    pub rows: Vec<String>,
+    pub cols: Vec<String>,
+}
+
+impl ProjectFile {
+    /// Snapshots everything a project file stores from the app
+    pub fn capture(state: &AppState) -> Self {
+        let table = &state.table;
+
+        let rows = table
+            .rows
+            .iter()
+            .map(|row| {
+                let bits = |values: &[BitValue]| -> String {
+                    values.iter().map(BitValue::to_char).collect()
+                };
+                format!("{}|{}", bits(&row.inputs), bits(&row.outputs))
+            })
+            .collect();
+
+        let window = &state.window_state;
+        Self {
+            version: CURRENT_VERSION,
+            rows,
+            window: WindowLayout {
+                table_view: window.table_view,
+                map_view: window.map_view,
+                expression_view: window.expression_view,
+                history_view: window.history_view,
+                tableau_view: window.tableau_view,
+            },
+            inputs: state
+                .variables
+                .inputs
+                .iter()
+                .map(|v| InputEntry {
+                    name: v.name.clone(),
+                    value: v.value.to_char(),
+                })
+                .collect(),
+            outputs: state
+                .variables
+                .outputs
+                .iter()
+                .map(|v| OutputEntry {
+                    name: v.name.clone(),
+                    value: v.value.to_char(),
+                    map: state.map.layouts.get(&v.name).map(|layout| MapAxes {
+                        maps: layout.maps.clone(),
+                        rows: layout.rows.clone(),
+                        cols: layout.cols.clone(),
+                    }),
+                })
+                .collect(),
+        }
+    }
+
+    /// Replaces the project part of the app state with this file
+    ///
+    /// The file is fully validated first, on error `state` is untouched.
+    pub fn restore(&self, state: &mut AppState) -> Result<(), ProjectError> {
+        let inputs: Vec<&str> = self.inputs.iter().map(|v| v.name.as_str()).collect();
+        let outputs: Vec<&str> = self.outputs.iter().map(|v| v.name.as_str()).collect();
+        gray::check_size(inputs.len())?;
+
+        let mut table = TruthTable::try_new(&inputs, &outputs)?;
+        if self.rows.len() != table.rows.len() {
+            return Err(ProjectError::Invalid(format!(
+                "expected {} rows for {} inputs, found {}",
+                table.rows.len(),
+                inputs.len(),
+                self.rows.len()
+            )));
+        }
+
+        for (index, (line, row)) in self.rows.iter().zip(&mut table.rows).enumerate() {
+            let invalid = || ProjectError::Invalid(format!("row {index} reads \"{line}\""));
+
+            let (input_bits, output_bits) = line.split_once('|').ok_or_else(invalid)?;
+            let expected: String = row.inputs.iter().map(BitValue::to_char).collect();
+            if input_bits.trim() != expected {
+                return Err(invalid());
+            }
+
+            let values = output_bits
+                .trim()
+                .chars()
+                .map(parse_bit)
+                .collect::<Option<Vec<_>>>()
+                .ok_or_else(invalid)?;
+            if values.len() != outputs.len() {
+                return Err(invalid());
+            }
+            row.outputs = values;
+        }
+
+        let value = |name: &str, c: char| {
+            parse_bit(c)
+                .ok_or_else(|| ProjectError::Invalid(format!("variable {name} has value '{c}'")))
+        };
+
+        let mut variables = VariableStore::default();
+        for v in &self.inputs {
+            variables.add(
+                v.name.clone(),
+                VariableKind::Input,
+                value(&v.name, v.value)?,
+            );
+        }
+        for v in &self.outputs {
+            variables.add(
+                v.name.clone(),
+                VariableKind::Output,
+                value(&v.name, v.value)?,
+            );
+        }
+
+        state.variables = variables;
+        state.table = table;
+        state.map = Default::default();
+        state.expression = Default::default();
+        state.tableau = Default::default();
+        state.selection = Default::default();
+        for v in &self.outputs {
+            if let Some(axes) = &v.map {
+                let layout = AxisLayout {
+                    maps: axes.maps.clone(),
+                    rows: axes.rows.clone(),
+                    cols: axes.cols.clone(),
+                };
+                state.map.layouts.insert(v.name.clone(), layout);
+            }
+        }
+
+        state.window_state.table_view = self.window.table_view;
+        state.window_state.map_view = self.window.map_view;
+        state.window_state.expression_view = self.window.expression_view;
+        state.window_state.history_view = self.window.history_view;
+        state.window_state.tableau_view = self.window.tableau_view;
+
+        Ok(())
+    }
+
+    /// Parses a project, upgrading files written by older versions
+    pub fn from_toml(src: &str) -> Result<Self, ProjectError> {
+        let mut doc: toml::Table = src.parse()?;
+
+        let version = doc
+            .get("version")
+            .and_then(toml::Value::as_integer)
+            .ok_or(ProjectError::MissingVersion)?;
+        if !(1..=CURRENT_VERSION as i64).contains(&version) {
+            return Err(ProjectError::UnsupportedVersion(version));
+        }
+
+        migrate::upgrade(&mut doc, version as u32);
+        Ok(doc.try_into()?)
+    }
+
+    pub fn to_toml(&self) -> Result<String, ProjectError> {
+        Ok(toml::to_string_pretty(self)?)
+    }
+
+    pub fn load(path: &Path) -> Result<Self, ProjectError> {
+        Self::from_toml(&fs::read_to_string(path)?)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), ProjectError> {
+        fs::write(path, self.to_toml()?)?;
+        Ok(())
+    }
+}
+
+fn parse_bit(c: char) -> Option<BitValue> {
+    match c {
+        '0' => Some(BitValue::Zero),
+        '1' => Some(BitValue::One),
+        'x' | 'X' | '-' => Some(BitValue::DontCare),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+fn sample_state() -> AppState {
+    use crate::ui::events::{self, Event, VariableEvent};
+
+    let mut state = AppState::default();
+    let add = |name: &str, kind| {
+        Event::Variable(VariableEvent::Add {
+            name: name.to_string(),
+            kind,
+            value: BitValue::DontCare,
+        })
+    };
+    events::dispatch_all(
+        &mut state,
+        [
+            add("A", VariableKind::Input),
+            add("B", VariableKind::Input),
+            add("F", VariableKind::Output),
+        ],
+    );
+    state.table.set(1, 0, BitValue::One);
+    state.table.set(2, 0, BitValue::Zero);
+    state.map.layouts.insert(
+        "F".to_string(),
+        AxisLayout {
+            maps: Vec::new(),
+            rows: vec!["B".to_string()],
+            cols: vec!["A".to_string()],
+        },
+    );
+    state.window_state.map_view = false;
+    state
+}
+
+#[test]
+fn round_trips_through_toml() {
+    let state = sample_state();
+    let file = ProjectFile::capture(&state);
+    let text = file.to_toml().unwrap();
+
+    // One row per line keeps diffs small
+    assert!(text.contains("\"01|1\""));
+    assert!(text.lines().any(|line| line.trim() == "\"10|0\","));
+
+    let loaded = ProjectFile::from_toml(&text).unwrap();
+    assert_eq!(loaded, file);
+
+    let mut restored = AppState::default();
+    loaded.restore(&mut restored).unwrap();
+    assert_eq!(restored.table.inputs, state.table.inputs);
+    assert_eq!(restored.table.terms(0, BitValue::One), vec![1]);
+    assert_eq!(restored.table.terms(0, BitValue::DontCare), vec![0, 3]);
+    assert_eq!(restored.variables.outputs[0].name, "F");
+    assert_eq!(restored.map.layouts.get("F"), state.map.layouts.get("F"));
+    assert!(!restored.window_state.map_view);
+}
+
+#[test]
+fn rejects_bad_versions_and_rows() {
+    assert!(matches!(
+        ProjectFile::from_toml("rows = []"),
+        Err(ProjectError::MissingVersion)
+    ));
+    assert!(matches!(
+        ProjectFile::from_toml("version = 99"),
+        Err(ProjectError::UnsupportedVersion(99))
+    ));
+
+    let mut file = ProjectFile::capture(&sample_state());
+    file.rows[2] = "11|0".to_string();
+
+    let mut state = sample_state();
+    assert!(matches!(
+        file.restore(&mut state),
+        Err(ProjectError::Invalid(_))
+    ));
+    // A failed restore leaves the app as it was
+    assert_eq!(state.table.terms(0, BitValue::Zero), vec![2]);
+}
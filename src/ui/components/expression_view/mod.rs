@@ -0,0 +1,360 @@
+use std::{collections::BTreeMap, ops::Range};
+
+use egui::{
+    Grid, Label, TextEdit, TopBottomPanel,
+    text::{LayoutJob, TextFormat},
+};
+
+use crate::{
+    app::AppState,
+    logic::{
+        canonical::Canonical,
+        expression::Expression,
+        notation::{self, Notation},
+        solver::{Cost, Form, Implicant, Method, Solution},
+        truth_table::TruthTable,
+        variable::BitValue,
+    },
+    ui::events::TableEvent,
+};
+
+#[derive(Clone, Debug, Default)]
+pub struct ExpressionViewState {
+    /// Text typed into each output's expression field, by output name
+    pub drafts: BTreeMap<String, String>,
+    /// Why the last applied draft was rejected, by output name
+    pub errors: BTreeMap<String, String>,
+    /// Formatted lines of each output, by output index
+    lines: Vec<Lines>,
+}
+
+/// One labeled expression of an output
+#[derive(Clone, Debug)]
+struct Line {
+    label: &'static str,
+    text: String,
+    cost: Cost,
+    /// The form of the minimal solution behind the text, if it is one
+    minimal: Option<Form>,
+}
+
+/// The lines of one output, only formatted again once the solutions, input
+/// names or notation they were built from change
+///
+/// At 16 inputs a canonical form lists tens of thousands of terms, far too
+/// many to format every frame.
+#[derive(Clone, Debug, Default)]
+struct Lines {
+    notation: Notation,
+    inputs: Vec<String>,
+    /// Minterms, cover and method of the SOP and POS solutions
+    sources: Vec<(Vec<u32>, Vec<Implicant>, Method)>,
+    lines: Vec<Line>,
+}
+
+impl Lines {
+    fn matches(&self, inputs: &[String], solutions: [&Solution; 2], notation: Notation) -> bool {
+        self.notation == notation
+            && self.inputs == inputs
+            && self.sources.len() == 2
+            && self
+                .sources
+                .iter()
+                .zip(solutions)
+                .all(|((minterms, cover, method), s)| {
+                    *minterms == s.minterms && *cover == s.cover && *method == s.method
+                })
+    }
+}
+
+/// Bottom panel listing every output in canonical and minimal form, with a
+/// field that rewrites the output column from a typed expression
+pub fn update(ctx: &egui::Context, app_state: &mut AppState) {
+    if !app_state.window_state.expression_view {
+        return;
+    }
+
+    TopBottomPanel::bottom("expression_view")
+        .resizable(true)
+        .default_height(220.0)
+        .show(ctx, |ui| {
+            ui.add_space(8.0);
+            ui.vertical_centered(|ui| {
+                ui.label("Expression View");
+            });
+            ui.separator();
+
+            egui::ScrollArea::vertical()
+                .auto_shrink([false, false])
+                .show(ui, |ui| render(ui, app_state));
+        });
+}
+
+fn render(ui: &mut egui::Ui, app_state: &mut AppState) {
+    let table = &app_state.table;
+    let state = &mut app_state.expression;
+    let events = &mut app_state.events;
+    let notation = app_state.preferences.notation;
+    let hovered = app_state.hovered_term;
+
+    if table.outputs.is_empty() {
+        ui.weak("Add an output variable to see its expressions");
+        return;
+    }
+
+    for output in 0..table.outputs.len() {
+        let solving = app_state.solutions.is_solving(table, output);
+        let solved = app_state.solutions.both(table, output);
+        let name = &table.outputs[output];
+        ui.add_space(4.0);
+        ui.horizontal(|ui| {
+            ui.strong(name);
+            if solving {
+                ui.weak("Solving…");
+            }
+        });
+        let Some(solutions) = solved else {
+            ui.separator();
+            continue;
+        };
+
+        if state.lines.len() <= output {
+            state.lines.resize_with(output + 1, Lines::default);
+        }
+        let lines = &mut state.lines[output];
+        if !lines.matches(&table.inputs, solutions, notation) {
+            *lines = build_lines(&table.inputs, solutions, notation);
+        }
+
+        Grid::new(("expression_lines", output))
+            .num_columns(4)
+            .striped(true)
+            .show(ui, |ui| {
+                for line in &lines.lines {
+                    // The term under the pointer on the map, if it is part of this line
+                    let highlight = hovered
+                        .filter(|h| h.output == output && line.minimal == Some(h.form))
+                        .and_then(|h| {
+                            let solution = solutions.iter().find(|s| s.form == h.form)?;
+                            term_range(&line.text, solution, h.implicant, &table.inputs, notation)
+                        });
+
+                    ui.label(line.label);
+                    ui.add(Label::new(highlighted_text(ui, &line.text, highlight)).truncate());
+                    ui.weak(format!(
+                        "{} terms, {} literals",
+                        line.cost.terms, line.cost.literals
+                    ));
+                    if ui.small_button("Copy").clicked() {
+                        ui.ctx().copy_text(line.text.clone());
+                    }
+                    ui.end_row();
+                }
+            });
+
+        ui.horizontal(|ui| {
+            let draft = state.drafts.entry(name.clone()).or_default();
+            let field = ui.add(
+                TextEdit::singleline(draft)
+                    .hint_text("A'B + C or Σm(1,3)")
+                    .desired_width(240.0),
+            );
+            let entered = field.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+
+            if ui.button("Apply").clicked() || entered {
+                match column(table, output, draft) {
+                    Ok(values) => {
+                        state.errors.remove(name);
+                        events.push_table(TableEvent::Assign { output, values });
+                    }
+                    Err(err) => {
+                        state.errors.insert(name.clone(), err);
+                    }
+                }
+            }
+        });
+        if let Some(err) = state.errors.get(name) {
+            ui.colored_label(ui.visuals().error_fg_color, err);
+        }
+        ui.separator();
+    }
+}
+
+/// Canonical and minimal lines of one output
+///
+/// The canonical forms list the same cells the solutions cover, the minterms
+/// of the SOP solution and the maxterms of the POS one.
+fn build_lines(inputs: &[String], solutions: [&Solution; 2], notation: Notation) -> Lines {
+    let n = inputs.len();
+    let mut lines = Vec::new();
+
+    for (label, solution) in ["Canonical SOP", "Canonical POS"]
+        .into_iter()
+        .zip(solutions)
+    {
+        let terms: Vec<Implicant> = solution
+            .minterms
+            .iter()
+            .copied()
+            .map(Implicant::from_minterm)
+            .collect();
+        lines.push(Line {
+            label,
+            text: notation::format_terms(&terms, solution.form, inputs, notation),
+            cost: Cost::of(&terms, n),
+            minimal: None,
+        });
+    }
+    for solution in solutions {
+        // Only the exact method guarantees the cover can't be beaten
+        let label = match (solution.method, solution.form) {
+            (Method::Heuristic, Form::Sop) => "Heuristic SOP",
+            (Method::Heuristic, Form::Pos) => "Heuristic POS",
+            (_, Form::Sop) => "Minimal SOP",
+            (_, Form::Pos) => "Minimal POS",
+        };
+        lines.push(Line {
+            label,
+            text: notation::format_solution(solution, inputs, notation),
+            cost: solution.cost(),
+            minimal: Some(solution.form),
+        });
+    }
+
+    Lines {
+        notation,
+        inputs: inputs.to_vec(),
+        sources: solutions
+            .iter()
+            .map(|s| (s.minterms.clone(), s.cover.clone(), s.method))
+            .collect(),
+        lines,
+    }
+}
+
+/// Byte range of `term` within `text`, the formatted cover of `solution`
+fn term_range(
+    text: &str,
+    solution: &Solution,
+    term: Implicant,
+    names: &[String],
+    notation: Notation,
+) -> Option<Range<usize>> {
+    let mut from = 0;
+    for t in &solution.cover {
+        let part = notation::format_terms(&[*t], solution.form, names, notation);
+        let start = from + text[from..].find(&part)?;
+        let end = start + part.len();
+        if *t == term {
+            return Some(start..end);
+        }
+        from = end;
+    }
+    None
+}
+
+/// Monospace text with the `highlight` range drawn on the selection color
+fn highlighted_text(ui: &egui::Ui, text: &str, highlight: Option<Range<usize>>) -> LayoutJob {
+    let font = TextFormat::simple(
+        egui::TextStyle::Monospace.resolve(ui.style()),
+        ui.visuals().text_color(),
+    );
+    let mut job = LayoutJob::default();
+    let Some(range) = highlight else {
+        job.append(text, 0.0, font);
+        return job;
+    };
+
+    let marked = TextFormat {
+        background: ui.visuals().selection.bg_fill,
+        color: ui.visuals().strong_text_color(),
+        ..font.clone()
+    };
+    job.append(&text[..range.start], 0.0, font.clone());
+    job.append(&text[range.clone()], 0.0, marked);
+    job.append(&text[range.end..], 0.0, font);
+    job
+}
+
+/// Evaluates a typed expression or Σm/ΠM list over the table's inputs
+///
+/// ### Returns
+/// The new value of every row of the output column, or a message when the
+/// text doesn't parse or names a variable the table doesn't have
+fn column(table: &TruthTable, output: usize, src: &str) -> Result<Vec<BitValue>, String> {
+    if Canonical::looks_like(src) {
+        let canonical = Canonical::parse(src).map_err(|err| err.to_string())?;
+        let mut table = table.clone();
+        canonical
+            .fill(&mut table, output)
+            .map_err(|err| err.to_string())?;
+        return Ok(table.rows.iter().map(|row| row.outputs[output]).collect());
+    }
+
+    let expr = Expression::parse_with(src, &table.inputs).map_err(|err| err.to_string())?;
+    if let Some(unknown) = expr.variables.get(table.inputs.len()) {
+        return Err(format!("{unknown} is not an input"));
+    }
+
+    Ok((0..table.rows.len() as u32)
+        .map(|row| {
+            if expr.evaluate(row) {
+                BitValue::One
+            } else {
+                BitValue::Zero
+            }
+        })
+        .collect())
+}
+
+#[test]
+fn evaluates_typed_expressions_over_inputs() {
+    use BitValue::*;
+
+    let table = TruthTable::new(&["A", "B"], &["F", "G"]);
+    assert_eq!(
+        column(&table, 1, "A'B + AB'"),
+        Ok(vec![Zero, One, One, Zero])
+    );
+    assert_eq!(column(&table, 0, "B"), Ok(vec![Zero, One, Zero, One]));
+    assert_eq!(
+        column(&table, 0, "Σm(3) + d(0)"),
+        Ok(vec![DontCare, Zero, Zero, One])
+    );
+
+    assert_eq!(
+        column(&table, 0, "A + C"),
+        Err("C is not an input".to_string())
+    );
+    assert!(column(&table, 0, "A +").is_err());
+    assert!(column(&table, 0, "Σm(4)").is_err());
+    assert_eq!(
+        column(&table, 0, "Σm(1"),
+        Err("expected ')' at 5".to_string())
+    );
+}
+
+#[test]
+fn locates_terms_in_formatted_covers() {
+    use crate::logic::solver::minimize;
+
+    // F = A'B + AC, formatted "A'B + AC"
+    let mut table = TruthTable::new(&["A", "B", "C"], &["F"]);
+    for row in 0..8 {
+        let value = [2, 3, 5, 7].contains(&row);
+        table.set(row, 0, BitValue::from_u8(value as u8));
+    }
+    let solution = minimize(&table, 0, Form::Sop);
+    let text = notation::format_solution(&solution, &table.inputs, Notation::Prime);
+
+    for term in &solution.cover {
+        let range = term_range(&text, &solution, *term, &table.inputs, Notation::Prime).unwrap();
+        let alone = notation::format_terms(&[*term], Form::Sop, &table.inputs, Notation::Prime);
+        assert_eq!(&text[range], alone);
+    }
+    let missing = Implicant::from_minterm(0);
+    assert_eq!(
+        term_range(&text, &solution, missing, &table.inputs, Notation::Prime),
+        None
+    );
+}
@@ -0,0 +1,76 @@
+use egui::{Button, RichText};
+
+use crate::{
+    app::AppState,
+    ui::events::{Event, EventQueue},
+};
+
+/// Floating list of undoable edits, clicking an entry undoes or redoes up
+/// to it
+pub fn update(ctx: &egui::Context, app_state: &mut AppState) {
+    let history = &app_state.history;
+    let events = &mut app_state.events;
+
+    egui::Window::new("History")
+        .open(&mut app_state.window_state.history_view)
+        .default_width(220.0)
+        .show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                if ui
+                    .add_enabled(history.can_undo(), Button::new("Undo"))
+                    .on_hover_text("Ctrl+Z")
+                    .clicked()
+                {
+                    events.push(Event::Undo);
+                }
+                if ui
+                    .add_enabled(history.can_redo(), Button::new("Redo"))
+                    .on_hover_text("Ctrl+Shift+Z")
+                    .clicked()
+                {
+                    events.push(Event::Redo);
+                }
+            });
+            ui.separator();
+
+            egui::ScrollArea::vertical()
+                .auto_shrink([false, true])
+                .show(ui, |ui| {
+                    let undo: Vec<&str> = history.undo_labels().collect();
+
+                    // The state before the oldest edit, then one row per edit
+                    // ending at the current state
+                    let start = RichText::new("Start");
+                    jump_button(ui, start, undo.is_empty(), -(undo.len() as isize), events);
+                    for (i, label) in undo.iter().enumerate() {
+                        let back = (undo.len() - i - 1) as isize;
+                        jump_button(ui, RichText::new(*label), back == 0, -back, events);
+                    }
+
+                    for (i, label) in history.redo_labels().enumerate() {
+                        let text = RichText::new(label).weak();
+                        jump_button(ui, text, false, i as isize + 1, events);
+                    }
+                });
+        });
+}
+
+/// An entry that undoes (`steps < 0`) or redoes (`steps > 0`) when clicked
+fn jump_button(
+    ui: &mut egui::Ui,
+    label: RichText,
+    current: bool,
+    steps: isize,
+    events: &mut EventQueue,
+) {
+    if ui.selectable_label(current, label).clicked() {
+        let step = if steps < 0 {
+            || Event::Undo
+        } else {
+            || Event::Redo
+        };
+        for _ in 0..steps.unsigned_abs() {
+            events.push(step());
+        }
+    }
+}
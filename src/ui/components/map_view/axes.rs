@@ -0,0 +1,254 @@
+use egui::{Button, CornerRadius, Frame, Grid, Id, Margin, Rect, vec2};
+
+use crate::logic::kmap::KMapFormat;
+
+/// Most variables the sub-map axis takes, beyond that the maps get too small
+const MAX_MAP_VARS: usize = 2;
+
+/// Variables on each axis of one output's map, by name
+///
+/// Stored by name so it survives inputs being moved. Names that stop being
+/// inputs are skipped and new inputs are placed automatically when the
+/// layout is turned into a [`KMapFormat`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AxisLayout {
+    pub maps: Vec<String>,
+    pub rows: Vec<String>,
+    pub cols: Vec<String>,
+}
+
+impl AxisLayout {
+    pub fn of(format: &KMapFormat) -> Self {
+        Self {
+            maps: format.map_vars.clone(),
+            rows: format.row_vars.clone(),
+            cols: format.col_vars.clone(),
+        }
+    }
+
+    /// The format for `inputs`, with new inputs added to the shorter of the
+    /// row and column axes
+    pub fn format(&self, inputs: &[String]) -> KMapFormat {
+        let mut placed: Vec<String> = Vec::new();
+        let mut keep = |names: &[String]| -> Vec<String> {
+            let mut out = Vec::new();
+            for name in names {
+                if inputs.contains(name) && !placed.contains(name) {
+                    placed.push(name.clone());
+                    out.push(name.clone());
+                }
+            }
+            out
+        };
+        let maps = keep(&self.maps);
+        let mut rows = keep(&self.rows);
+        let mut cols = keep(&self.cols);
+
+        for name in inputs {
+            if maps.contains(name) || rows.contains(name) || cols.contains(name) {
+                continue;
+            }
+            if rows.len() < cols.len() {
+                rows.push(name.clone());
+            } else {
+                cols.push(name.clone());
+            }
+        }
+
+        KMapFormat::new(inputs, &maps, &rows, &cols).unwrap_or_else(|_| KMapFormat::auto(inputs))
+    }
+
+    /// Follows an input being renamed
+    pub fn rename(&mut self, old: &str, new: &str) {
+        for name in self
+            .maps
+            .iter_mut()
+            .chain(&mut self.rows)
+            .chain(&mut self.cols)
+        {
+            if name == old {
+                *name = new.to_string();
+            }
+        }
+    }
+
+    fn axis_mut(&mut self, axis: Axis) -> &mut Vec<String> {
+        match axis {
+            Axis::Maps => &mut self.maps,
+            Axis::Rows => &mut self.rows,
+            Axis::Cols => &mut self.cols,
+        }
+    }
+
+    /// Moves the variable at `from` to position `to` of another or the same axis
+    fn move_chip(&mut self, from: Chip, to: Axis, index: usize) {
+        if from.index >= self.axis_mut(from.axis).len() {
+            return;
+        }
+        let name = self.axis_mut(from.axis).remove(from.index);
+
+        // The chip itself no longer takes a slot before the drop point
+        let index = if from.axis == to && index > from.index {
+            index - 1
+        } else {
+            index
+        };
+        let target = self.axis_mut(to);
+        target.insert(index.min(target.len()), name);
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Axis {
+    Maps,
+    Rows,
+    Cols,
+}
+
+impl Axis {
+    const ALL: [Axis; 3] = [Axis::Maps, Axis::Rows, Axis::Cols];
+
+    fn label(self) -> &'static str {
+        match self {
+            Axis::Maps => "Sub-maps",
+            Axis::Rows => "Rows",
+            Axis::Cols => "Columns",
+        }
+    }
+}
+
+/// Drag payload: a variable chip and where it was picked up
+#[derive(Clone, Copy, Debug)]
+struct Chip {
+    axis: Axis,
+    index: usize,
+}
+
+/// A change made in the axis editor
+pub enum AxesEdit {
+    /// Go back to the automatic layout
+    Auto,
+    Set(AxisLayout),
+}
+
+/// Drop zones for each axis holding one draggable chip per variable
+///
+/// Chips are dropped before the chip under the pointer, or at the end of the
+/// axis. The presets replace the whole layout.
+pub fn editor(ui: &mut egui::Ui, format: &KMapFormat, inputs: &[String]) -> Option<AxesEdit> {
+    let mut layout = AxisLayout::of(format);
+    let mut edit = None;
+    ui.spacing_mut().item_spacing = vec2(4.0, 4.0);
+
+    Grid::new("map_axes").num_columns(2).show(ui, |ui| {
+        for axis in Axis::ALL {
+            ui.label(axis.label());
+
+            let names = layout.axis_mut(axis).clone();
+            let mut chips: Vec<Rect> = Vec::new();
+            let zone = Frame::new()
+                .inner_margin(Margin::same(4))
+                .corner_radius(CornerRadius::same(4))
+                .fill(ui.visuals().extreme_bg_color);
+            let (_, dropped) = ui.dnd_drop_zone::<Chip, _>(zone, |ui| {
+                ui.set_min_width(160.0);
+                ui.horizontal(|ui| {
+                    if names.is_empty() {
+                        ui.weak("drop variables here");
+                    }
+                    for (index, name) in names.iter().enumerate() {
+                        let id = Id::new(("map_axis_chip", name));
+                        let chip = ui.dnd_drag_source(id, Chip { axis, index }, |ui| {
+                            Frame::new()
+                                .inner_margin(Margin::symmetric(6, 2))
+                                .corner_radius(CornerRadius::same(8))
+                                .fill(ui.visuals().widgets.inactive.bg_fill)
+                                .show(ui, |ui| ui.label(name));
+                        });
+                        chips.push(chip.response.rect);
+                    }
+                });
+            });
+
+            if let Some(chip) = dropped {
+                let full =
+                    axis == Axis::Maps && chip.axis != Axis::Maps && names.len() >= MAX_MAP_VARS;
+                if !full {
+                    let x = ui
+                        .input(|i| i.pointer.interact_pos())
+                        .map_or(f32::INFINITY, |pos| pos.x);
+                    let index = chips.iter().filter(|rect| rect.center().x < x).count();
+                    layout.move_chip(*chip, axis, index);
+                    edit = Some(AxesEdit::Set(layout.clone()));
+                }
+            }
+            ui.end_row();
+        }
+    });
+
+    ui.horizontal(|ui| {
+        if ui.button("Auto").clicked() {
+            edit = Some(AxesEdit::Auto);
+        }
+        if ui.button("Swap rows and columns").clicked() {
+            let mut swapped = AxisLayout::of(format);
+            std::mem::swap(&mut swapped.rows, &mut swapped.cols);
+            edit = Some(AxesEdit::Set(swapped));
+        }
+
+        let stacked = KMapFormat::stacked(inputs).ok();
+        if ui
+            .add_enabled(stacked.is_some(), Button::new("Stacked 4×4"))
+            .on_disabled_hover_text("Needs 5 or 6 inputs")
+            .clicked()
+            && let Some(stacked) = stacked
+        {
+            edit = Some(AxesEdit::Set(AxisLayout::of(&stacked)));
+        }
+    });
+
+    edit
+}
+
+#[test]
+fn repairs_layouts_when_inputs_change() {
+    let inputs = |names: &[&str]| -> Vec<String> { names.iter().map(|n| n.to_string()).collect() };
+    let layout = AxisLayout {
+        maps: Vec::new(),
+        rows: inputs(&["C", "A"]),
+        cols: inputs(&["B"]),
+    };
+
+    let format = layout.format(&inputs(&["A", "B", "C"]));
+    assert_eq!(format.row_vars, vec!["C", "A"]);
+    assert_eq!(format.col_vars, vec!["B"]);
+
+    // A is gone and D is new, it joins the shorter axis
+    let format = layout.format(&inputs(&["B", "C", "D"]));
+    assert_eq!(format.row_vars, vec!["C"]);
+    assert_eq!(format.col_vars, vec!["B", "D"]);
+
+    let mut moved = layout.clone();
+    moved.move_chip(
+        Chip {
+            axis: Axis::Rows,
+            index: 0,
+        },
+        Axis::Cols,
+        0,
+    );
+    assert_eq!(moved.rows, vec!["A"]);
+    assert_eq!(moved.cols, vec!["C", "B"]);
+
+    // Dropping a chip after itself keeps its place
+    let mut same = layout.clone();
+    same.move_chip(
+        Chip {
+            axis: Axis::Rows,
+            index: 0,
+        },
+        Axis::Rows,
+        1,
+    );
+    assert_eq!(same.rows, vec!["C", "A"]);
+}
@@ -0,0 +1,261 @@
+use egui::{Align2, FontId, Pos2, Rect, Sense, Stroke, StrokeKind, Vec2, vec2};
+
+use crate::logic::{gray, kmap::KMap, variable::BitValue};
+
+const PAD: f32 = 6.0;
+const MIN_CELL: f32 = 32.0;
+
+/// Geometry of a painted map, shared by the grid and anything drawn over it
+#[derive(Clone, Copy, Debug)]
+pub struct GridLayout {
+    /// Top-left corner of cell `(0, 0)`
+    pub origin: Pos2,
+    pub cell: f32,
+    pub rows: usize,
+    pub cols: usize,
+    /// Rows and columns of one sub-map, the whole grid for a single map
+    pub sub_size: (usize, usize),
+    /// Space left between neighbouring sub-maps
+    pub gap: Vec2,
+}
+
+impl GridLayout {
+    pub fn cell_rect(&self, row: usize, col: usize) -> Rect {
+        let (sub_rows, sub_cols) = self.sub_size;
+        let gaps = vec2(
+            (col / sub_cols) as f32 * self.gap.x,
+            (row / sub_rows) as f32 * self.gap.y,
+        );
+        Rect::from_min_size(
+            self.origin + vec2(col as f32 * self.cell, row as f32 * self.cell) + gaps,
+            Vec2::splat(self.cell),
+        )
+    }
+
+    /// The area of the sub-map holding cell `(row, col)`
+    pub fn map_rect(&self, row: usize, col: usize) -> Rect {
+        let (sub_rows, sub_cols) = self.sub_size;
+        let (row, col) = (row - row % sub_rows, col - col % sub_cols);
+        self.cell_rect(row, col)
+            .union(self.cell_rect(row + sub_rows - 1, col + sub_cols - 1))
+    }
+
+    /// The cell nearest to `pos`, clamped to the grid
+    pub fn nearest_cell(&self, pos: Pos2) -> (usize, usize) {
+        let local = pos - self.origin;
+        let nearest = |offset: f32, len: usize, sub: usize, gap: f32| {
+            let block = sub as f32 * self.cell + gap;
+            let map = (offset.max(0.0) / block) as usize;
+            let within = offset - map as f32 * block;
+            (map * sub + ((within.max(0.0) / self.cell) as usize).min(sub - 1)).min(len - 1)
+        };
+        (
+            nearest(local.y, self.rows, self.sub_size.0, self.gap.y),
+            nearest(local.x, self.cols, self.sub_size.1, self.gap.x),
+        )
+    }
+
+    /// The `(row, col)` under `pos`, if it is inside the grid and not in a gap
+    pub fn cell_at(&self, pos: Pos2) -> Option<(usize, usize)> {
+        let local = pos - self.origin;
+        let at = |offset: f32, len: usize, sub: usize, gap: f32| {
+            if offset < 0.0 {
+                return None;
+            }
+            let block = sub as f32 * self.cell + gap;
+            let map = (offset / block) as usize;
+            let within = offset - map as f32 * block;
+            let index = map * sub + (within / self.cell) as usize;
+            (within < sub as f32 * self.cell && index < len).then_some(index)
+        };
+        Some((
+            at(local.y, self.rows, self.sub_size.0, self.gap.y)?,
+            at(local.x, self.cols, self.sub_size.1, self.gap.x)?,
+        ))
+    }
+}
+
+/// What happened on a painted map
+pub struct GridResponse {
+    pub layout: GridLayout,
+    /// The `(row, col)` under the pointer
+    pub hovered: Option<(usize, usize)>,
+    /// Minterm of a clicked cell
+    pub clicked: Option<u32>,
+    pub response: egui::Response,
+}
+
+/// Draws the map with gray code headers, outlining the `selected` minterm
+///
+/// Stacked layouts get a title above each sub-map and a gap between them.
+pub fn render(ui: &mut egui::Ui, kmap: &KMap, selected: Option<u32>) -> GridResponse {
+    let rbits = kmap.format.row_vars.len();
+    let cbits = kmap.format.col_vars.len();
+    let (map_rows, map_cols) = kmap.format.map_grid();
+    let (sub_rows, sub_cols) = kmap.format.sub_size();
+
+    let font = FontId::monospace(14.0);
+    let small = FontId::monospace(9.0);
+    let visuals = ui.visuals().clone();
+
+    let row_label = kmap.format.row_vars.join("");
+    let col_label = kmap.format.col_vars.join("");
+    let text_size = |text: &str| {
+        ui.painter()
+            .layout_no_wrap(text.to_string(), font.clone(), visuals.text_color())
+            .size()
+    };
+
+    let row_code_w = text_size(&gray::format_bits(0, rbits)).x;
+    let col_code_w = text_size(&gray::format_bits(0, cbits)).x;
+    let line_h = text_size("0").y;
+
+    let header_w = row_code_w.max(text_size(&row_label).x) + 2.0 * PAD;
+    let header_h = 2.0 * line_h + 2.0 * PAD;
+    let cell = MIN_CELL.max(col_code_w + 2.0 * PAD);
+
+    // Room for the sub-map titles above the first row of maps and between rows
+    let title_h = if kmap.format.is_stacked() {
+        line_h + PAD
+    } else {
+        0.0
+    };
+    let gap = vec2(
+        if map_cols > 1 { cell / 2.0 } else { 0.0 },
+        if map_rows > 1 { title_h + PAD } else { 0.0 },
+    );
+
+    let size = vec2(
+        header_w + kmap.cols as f32 * cell + (map_cols - 1) as f32 * gap.x,
+        title_h + header_h + kmap.rows as f32 * cell + (map_rows - 1) as f32 * gap.y,
+    );
+    let (rect, response) = ui.allocate_exact_size(size, Sense::click_and_drag());
+
+    let corner = rect.min + vec2(0.0, title_h);
+    let layout = GridLayout {
+        origin: corner + vec2(header_w, header_h),
+        cell,
+        rows: kmap.rows,
+        cols: kmap.cols,
+        sub_size: (sub_rows, sub_cols),
+        gap,
+    };
+
+    let painter = ui.painter_at(rect);
+    let stroke = Stroke::new(1.0, visuals.widgets.noninteractive.bg_stroke.color);
+
+    // Corner with the row variables below the diagonal and columns above it
+    painter.line_segment([corner, layout.origin], stroke);
+    painter.text(
+        corner + vec2(PAD, header_h - PAD),
+        Align2::LEFT_BOTTOM,
+        &row_label,
+        font.clone(),
+        visuals.strong_text_color(),
+    );
+    painter.text(
+        corner + vec2(header_w - PAD, PAD),
+        Align2::RIGHT_TOP,
+        &col_label,
+        font.clone(),
+        visuals.strong_text_color(),
+    );
+
+    if kmap.format.is_stacked() {
+        for map_row in 0..map_rows {
+            for map_col in 0..map_cols {
+                let map = layout.map_rect(map_row * sub_rows, map_col * sub_cols);
+                // The first row of maps has the column codes between title and cells
+                let above = if map_row == 0 {
+                    header_h + title_h / 2.0
+                } else {
+                    gap.y / 2.0
+                };
+                painter.text(
+                    map.center_top() - vec2(0.0, above),
+                    Align2::CENTER_CENTER,
+                    kmap.format.map_label(map_row, map_col),
+                    font.clone(),
+                    visuals.strong_text_color(),
+                );
+            }
+        }
+    }
+
+    for col in 0..kmap.cols {
+        let code = gray::format_bits(kmap.format.col_code(col), cbits);
+        let center = layout.cell_rect(0, col).center_top() - vec2(0.0, PAD + line_h / 2.0);
+        painter.text(
+            center,
+            Align2::CENTER_CENTER,
+            code,
+            font.clone(),
+            visuals.text_color(),
+        );
+    }
+
+    for row in 0..kmap.rows {
+        let code = gray::format_bits(kmap.format.row_code(row), rbits);
+        let center = layout.cell_rect(row, 0).left_center() - vec2(PAD, 0.0);
+        painter.text(
+            center,
+            Align2::RIGHT_CENTER,
+            code,
+            font.clone(),
+            visuals.text_color(),
+        );
+    }
+
+    let hovered = response.hover_pos().and_then(|pos| layout.cell_at(pos));
+
+    for (row, values) in kmap.grid.iter().enumerate() {
+        for (col, value) in values.iter().enumerate() {
+            let cell_rect = layout.cell_rect(row, col);
+
+            let fill = if hovered == Some((row, col)) {
+                visuals.widgets.hovered.weak_bg_fill
+            } else {
+                match value {
+                    BitValue::One => visuals.selection.bg_fill.gamma_multiply(0.35),
+                    BitValue::DontCare => visuals.faint_bg_color,
+                    BitValue::Zero => visuals.extreme_bg_color,
+                }
+            };
+
+            let border = if selected == Some(kmap.minterm(row, col)) {
+                Stroke::new(2.0, visuals.selection.bg_fill)
+            } else {
+                stroke
+            };
+            painter.rect(cell_rect, 0.0, fill, border, StrokeKind::Inside);
+            painter.text(
+                cell_rect.center(),
+                Align2::CENTER_CENTER,
+                value.to_char(),
+                font.clone(),
+                visuals.text_color(),
+            );
+            painter.text(
+                cell_rect.min + vec2(2.0, 1.0),
+                Align2::LEFT_TOP,
+                kmap.minterm(row, col),
+                small.clone(),
+                visuals.weak_text_color(),
+            );
+        }
+    }
+
+    let clicked = response
+        .clicked()
+        .then(|| response.interact_pointer_pos())
+        .flatten()
+        .and_then(|pos| layout.cell_at(pos))
+        .map(|(row, col)| kmap.minterm(row, col));
+
+    GridResponse {
+        layout,
+        hovered,
+        clicked,
+        response,
+    }
+}
@@ -0,0 +1,46 @@
+use egui::{Shape, Stroke, StrokeKind};
+
+use crate::logic::{kmap::KMap, solver::hazard::Hazard};
+
+use super::grid::GridLayout;
+
+/// Outlines both cells of every hazard and joins them, with a dashed line
+/// when the cells only touch across an edge or by reflection
+pub fn paint(ui: &egui::Ui, layout: &GridLayout, kmap: &KMap, hazards: &[Hazard]) {
+    let painter = ui.painter();
+    let color = ui.visuals().warn_fg_color;
+    let stroke = Stroke::new(2.0, color);
+
+    for hazard in hazards {
+        let (r1, c1) = kmap.cell(hazard.from);
+        let (r2, c2) = kmap.cell(hazard.to);
+        let (a, b) = (layout.cell_rect(r1, c1), layout.cell_rect(r2, c2));
+
+        painter.rect_stroke(a.shrink(3.0), 2.0, stroke, StrokeKind::Inside);
+        painter.rect_stroke(b.shrink(3.0), 2.0, stroke, StrokeKind::Inside);
+
+        let points = [a.center(), b.center()];
+        if r1.abs_diff(r2) + c1.abs_diff(c2) == 1 {
+            painter.line_segment(points, stroke);
+        } else {
+            painter.extend(Shape::dashed_line(&points, stroke, 6.0, 4.0));
+        }
+    }
+}
+
+/// One line per hazard touching `cell`, for the hover text
+pub fn describe(kmap: &KMap, hazards: &[Hazard], cell: (usize, usize)) -> Option<String> {
+    let minterm = kmap.minterm(cell.0, cell.1);
+    let lines: Vec<String> = hazards
+        .iter()
+        .filter(|h| h.from == minterm || h.to == minterm)
+        .map(|h| {
+            format!(
+                "Hazard between {} and {} while {} changes",
+                h.from, h.to, kmap.variables[h.variable]
+            )
+        })
+        .collect();
+
+    (!lines.is_empty()).then(|| lines.join("\n"))
+}
@@ -0,0 +1,169 @@
+use egui::{Color32, Rect, Shape, Stroke, StrokeKind, ecolor::Hsva};
+
+use crate::logic::{
+    kmap::{GroupRect, KMapFormat},
+    solver::{Implicant, ImplicantRole, Solution},
+};
+
+use super::grid::GridLayout;
+
+/// Which implicants the map draws loops for
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LoopFilter {
+    None,
+    /// Every prime implicant
+    Primes,
+    Essential,
+    /// The selected minimal cover
+    #[default]
+    Cover,
+}
+
+impl LoopFilter {
+    pub const ALL: [LoopFilter; 4] = [
+        LoopFilter::None,
+        LoopFilter::Primes,
+        LoopFilter::Essential,
+        LoopFilter::Cover,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            LoopFilter::None => "No loops",
+            LoopFilter::Primes => "All primes",
+            LoopFilter::Essential => "Essential primes",
+            LoopFilter::Cover => "Final cover",
+        }
+    }
+
+    /// The implicants to draw, each with its index among the primes so a
+    /// term keeps its color when the filter changes
+    pub fn pick(self, solution: &Solution) -> Vec<(usize, Implicant)> {
+        solution
+            .roles()
+            .into_iter()
+            .enumerate()
+            .filter(|(_, (_, role))| match self {
+                LoopFilter::None => false,
+                LoopFilter::Primes => true,
+                LoopFilter::Essential => *role == ImplicantRole::Essential,
+                LoopFilter::Cover => *role != ImplicantRole::Redundant,
+            })
+            .map(|(i, (term, _))| (i, term))
+            .collect()
+    }
+}
+
+/// A distinct color per implicant index, spread around the hue circle
+pub fn color(index: usize) -> Color32 {
+    // Golden ratio steps keep neighbouring indices far apart
+    let hue = (index as f32 * 0.618_034).fract();
+    Hsva::new(hue, 0.75, 0.85, 1.0).into()
+}
+
+/// Pixel rect of one piece of a group, before insetting
+fn piece_rect(layout: &GridLayout, piece: &GroupRect) -> Rect {
+    let min = layout.cell_rect(piece.row, piece.col).min;
+    let max = layout
+        .cell_rect(piece.row + piece.rows - 1, piece.col + piece.cols - 1)
+        .max;
+    Rect::from_min_max(min, max)
+}
+
+/// Draws a rounded loop around every piece of each implicant
+///
+/// Sides that wrap around the map are pushed past the grid edge and clipped
+/// there, leaving the loop open the way textbooks draw it. On stacked maps a
+/// dashed line joins the pieces a group has on neighbouring sub-maps. The
+/// `hovered` implicant gets a thicker outline and a tinted fill.
+pub fn paint(
+    ui: &egui::Ui,
+    layout: &GridLayout,
+    format: &KMapFormat,
+    terms: &[(usize, Implicant)],
+    hovered: Option<Implicant>,
+) {
+    let painter = ui.painter();
+    let cell = layout.cell;
+
+    for (slot, &(index, term)) in terms.iter().enumerate() {
+        let color = color(index);
+        let highlighted = hovered == Some(term);
+        let stroke = Stroke::new(if highlighted { 3.5 } else { 2.0 }, color);
+        let fill = if highlighted {
+            color.gamma_multiply(0.2)
+        } else {
+            Color32::TRANSPARENT
+        };
+
+        // Nested loops are inset by different amounts so their edges don't overlap
+        let inset = 3.0 + (slot % 4) as f32 * 2.5;
+        let pieces = format.group_rects(&term);
+        for piece in &pieces {
+            let mut rect = piece_rect(layout, piece).shrink(inset);
+            let open = piece.open;
+            let reach = cell / 2.0;
+            if open.top {
+                rect.min.y -= reach;
+            }
+            if open.bottom {
+                rect.max.y += reach;
+            }
+            if open.left {
+                rect.min.x -= reach;
+            }
+            if open.right {
+                rect.max.x += reach;
+            }
+
+            painter
+                .with_clip_rect(layout.map_rect(piece.row, piece.col))
+                .rect(rect, cell / 3.0, fill, stroke, StrokeKind::Middle);
+        }
+
+        for (a, b) in format.map_links(&pieces) {
+            let points = [
+                piece_rect(layout, &pieces[a]).center(),
+                piece_rect(layout, &pieces[b]).center(),
+            ];
+            let stroke = Stroke::new(stroke.width * 0.75, color);
+            painter.extend(Shape::dashed_line(&points, stroke, 6.0, 4.0));
+        }
+    }
+}
+
+/// The drawn implicant covering `minterm`, preferring the smallest group
+pub fn hit(terms: &[(usize, Implicant)], minterm: u32) -> Option<Implicant> {
+    terms
+        .iter()
+        .map(|&(_, term)| term)
+        .filter(|term| term.covers(minterm))
+        .min_by_key(|term| term.mask.count_ones())
+}
+
+#[test]
+fn filters_loops_by_role() {
+    use crate::logic::{
+        solver::{Form, minimize},
+        truth_table::TruthTable,
+        variable::BitValue,
+    };
+
+    // F(A,B,C) = Σm(0,1,5,7): A'B' and AC are essential, B'C is redundant
+    let mut table = TruthTable::new(&["A", "B", "C"], &["F"]);
+    for row in 0..8 {
+        let value = [0, 1, 5, 7].contains(&row);
+        table.set(row, 0, BitValue::from_u8(value as u8));
+    }
+    let solution = minimize(&table, 0, Form::Sop);
+
+    assert_eq!(LoopFilter::Primes.pick(&solution).len(), 3);
+    assert_eq!(LoopFilter::Essential.pick(&solution).len(), 2);
+    assert_eq!(LoopFilter::Cover.pick(&solution).len(), 2);
+    assert!(LoopFilter::None.pick(&solution).is_empty());
+
+    // Cell 2 is outside every loop, 5 sits in both AC and B'C
+    let primes = LoopFilter::Primes.pick(&solution);
+    assert_eq!(hit(&primes, 2), None);
+    assert!(hit(&primes, 5).is_some_and(|t| t.covers(5)));
+}
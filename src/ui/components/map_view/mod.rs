@@ -1,10 +1,296 @@
-use egui::Vec2;
+use std::collections::BTreeMap;
 
-pub fn render(ui: &mut egui::Ui) {
+use egui::{ComboBox, RichText, Vec2};
+
+use crate::{
+    app::AppState,
+    logic::{
+        self,
+        kmap::{KMap, KMapFormat},
+        notation,
+        solver::{Form, Solution, hazard::Hazard},
+    },
+    ui::{
+        events::TableEvent,
+        selection::{HoveredTerm, Selection},
+        solutions::HazardCheck,
+    },
+};
+
+mod axes;
+mod grid;
+mod hazards;
+mod loops;
+mod practice;
+
+use axes::AxesEdit;
+pub use axes::AxisLayout;
+pub use loops::LoopFilter;
+pub use practice::PracticeState;
+
+#[derive(Clone, Debug, Default)]
+pub struct MapViewState {
+    /// Index of the output shown on the map
+    pub output: usize,
+    /// Axis layout chosen per output name, automatic when absent
+    pub layouts: BTreeMap<String, AxisLayout>,
+    /// Form whose cover the map overlays are drawn from
+    pub form: Form,
+    /// Which implicants get a loop
+    pub loops: LoopFilter,
+    /// Highlight static hazards of the minimal cover
+    pub show_hazards: bool,
+    /// Hand-drawn groups checked against the solver
+    pub practice: PracticeState,
+}
+
+impl MapViewState {
+    /// The map format of `output`, automatic unless a layout was picked
+    pub fn format(&self, output: &str, inputs: &[String]) -> KMapFormat {
+        self.layouts
+            .get(output)
+            .map_or_else(|| KMapFormat::auto(inputs), |layout| layout.format(inputs))
+    }
+}
+
+pub fn render(ui: &mut egui::Ui, app_state: &mut AppState) {
+    app_state.hovered_term = None;
     ui.spacing_mut().item_spacing = Vec2::ZERO;
     ui.vertical_centered(|ui| {
         ui.add_space(8.0);
         ui.label("Map View");
         ui.separator();
     });
-}
\ No newline at end of file
+
+    let table = &app_state.table;
+    let notation = app_state.preferences.notation;
+    let state = &mut app_state.map;
+    let events = &mut app_state.events;
+    let selection = &mut app_state.selection;
+
+    if table.outputs.is_empty() {
+        ui.vertical_centered(|ui| {
+            ui.add_space(8.0);
+            ui.weak("Add an output variable to see its map");
+        });
+        return;
+    }
+    state.output = state.output.min(table.outputs.len() - 1);
+
+    ui.add_space(4.0);
+    ui.horizontal(|ui| {
+        ui.add_space(8.0);
+        if table.outputs.len() > 1 {
+            ui.label("Output: ");
+            ComboBox::from_id_salt("map_output")
+                .selected_text(&table.outputs[state.output])
+                .show_ui(ui, |ui| {
+                    for (i, name) in table.outputs.iter().enumerate() {
+                        ui.selectable_value(&mut state.output, i, name);
+                    }
+                });
+            ui.add_space(8.0);
+        }
+
+        ComboBox::from_id_salt("map_form")
+            .selected_text(form_label(state.form))
+            .show_ui(ui, |ui| {
+                for form in [Form::Sop, Form::Pos] {
+                    ui.selectable_value(&mut state.form, form, form_label(form));
+                }
+            });
+        ui.add_space(8.0);
+        ComboBox::from_id_salt("map_loops")
+            .selected_text(state.loops.label())
+            .show_ui(ui, |ui| {
+                for filter in LoopFilter::ALL {
+                    ui.selectable_value(&mut state.loops, filter, filter.label());
+                }
+            });
+        ui.add_space(8.0);
+        ui.checkbox(&mut state.show_hazards, "Hazards");
+        ui.add_space(8.0);
+        ui.checkbox(&mut state.practice.enabled, "Practice");
+    });
+
+    let name = table.outputs[state.output].clone();
+    let current = state.format(&name, &table.inputs);
+    ui.add_space(4.0);
+    ui.horizontal(|ui| {
+        ui.add_space(8.0);
+        ui.vertical(|ui| {
+            egui::CollapsingHeader::new("Axes")
+                .id_salt("map_axes")
+                .show(ui, |ui| match axes::editor(ui, &current, &table.inputs) {
+                    Some(AxesEdit::Auto) => {
+                        state.layouts.remove(&name);
+                    }
+                    Some(AxesEdit::Set(layout)) => {
+                        state.layouts.insert(name.clone(), layout);
+                    }
+                    None => {}
+                });
+        });
+    });
+
+    let format = state.format(&name, &table.inputs);
+    match KMap::from_table(table, format, Some(state.output)) {
+        Ok(kmap) => {
+            let output = state.output;
+            let practicing = state.practice.enabled;
+            let show_hazards = state.show_hazards && !practicing;
+            let solved = if show_hazards {
+                app_state
+                    .solutions
+                    .hazards(table, output, state.form)
+                    .map(|(solution, check)| (solution, Some(check)))
+            } else {
+                app_state
+                    .solutions
+                    .solution(table, output, state.form)
+                    .map(|solution| (solution, None))
+            };
+            let Some((solution, check)) = solved else {
+                ui.vertical_centered(|ui| {
+                    ui.add_space(8.0);
+                    ui.weak("Solving…");
+                });
+                return;
+            };
+            let hazards: &[Hazard] = check.map_or(&[], |c| &c.hazards);
+            // Practice hides the solver's loops so they don't give the answer away
+            let terms = if practicing {
+                Vec::new()
+            } else {
+                state.loops.pick(solution)
+            };
+            state.practice.retarget(output, state.form);
+            let checks: Vec<_> = state
+                .practice
+                .groups
+                .iter()
+                .map(|group| logic::practice::check_group(group, solution))
+                .collect();
+            let mut hovered_term = None;
+
+            egui::ScrollArea::both()
+                .auto_shrink([false, false])
+                .show(ui, |ui| {
+                    ui.add_space(8.0);
+                    ui.horizontal(|ui| {
+                        ui.add_space(8.0);
+                        let selected = match *selection {
+                            Selection::Cell {
+                                row,
+                                output: Some(o),
+                            } if o == output => Some(row as u32),
+                            _ => None,
+                        };
+                        let grid = grid::render(ui, &kmap, selected);
+                        let hovered = grid
+                            .hovered
+                            .and_then(|(row, col)| loops::hit(&terms, kmap.minterm(row, col)));
+                        loops::paint(ui, &grid.layout, &kmap.format, &terms, hovered);
+                        if practicing {
+                            practice::paint(ui, &grid, &kmap, &checks);
+                            practice::handle_input(ui, &mut state.practice, &grid, &kmap);
+                        }
+                        hazards::paint(ui, &grid.layout, &kmap, hazards);
+                        hovered_term = hovered.map(|implicant| HoveredTerm {
+                            output,
+                            form: solution.form,
+                            implicant,
+                        });
+
+                        if let Some(text) = grid
+                            .hovered
+                            .and_then(|cell| hazards::describe(&kmap, hazards, cell))
+                        {
+                            egui::Tooltip::always_open(
+                                ui.ctx().clone(),
+                                ui.layer_id(),
+                                ui.id().with("hazard_tip"),
+                                egui::PopupAnchor::Pointer,
+                            )
+                            .show(|ui| ui.label(text));
+                        }
+
+                        if let Some(minterm) = grid.clicked.filter(|_| !practicing) {
+                            *selection = Selection::Cell {
+                                row: minterm as usize,
+                                output: Some(output),
+                            };
+                            events.push_table(TableEvent::Toggle {
+                                row: minterm as usize,
+                                output,
+                            });
+                        }
+                    });
+
+                    if practicing {
+                        ui.add_space(8.0);
+                        ui.horizontal(|ui| {
+                            ui.add_space(8.0);
+                            practice::panel(
+                                ui,
+                                &mut state.practice,
+                                &checks,
+                                solution,
+                                &table.inputs,
+                                notation,
+                            );
+                        });
+                    }
+                    if let Some(check) = check {
+                        ui.add_space(8.0);
+                        ui.horizontal(|ui| {
+                            ui.add_space(8.0);
+                            hazard_summary(ui, solution, check, &table.inputs, notation);
+                        });
+                    }
+                });
+            app_state.hovered_term = hovered_term;
+        }
+        Err(err) => {
+            ui.vertical_centered(|ui| {
+                ui.add_space(8.0);
+                ui.colored_label(ui.visuals().error_fg_color, err.to_string());
+            });
+        }
+    }
+}
+
+fn form_label(form: Form) -> &'static str {
+    match form {
+        Form::Sop => "SOP",
+        Form::Pos => "POS",
+    }
+}
+
+/// Hazard count and the cover with the consensus terms added
+fn hazard_summary(
+    ui: &mut egui::Ui,
+    solution: &Solution,
+    check: &HazardCheck,
+    names: &[String],
+    notation: notation::Notation,
+) {
+    ui.vertical(|ui| {
+        let kind = match solution.form {
+            Form::Sop => "static-1",
+            Form::Pos => "static-0",
+        };
+        let count = check.hazards.len();
+        if count == 0 {
+            ui.weak(format!("No {kind} hazards"));
+            return;
+        }
+
+        ui.colored_label(
+            ui.visuals().warn_fg_color,
+            format!("{count} {kind} hazard(s)"),
+        );
+        let text = notation::format_terms(&check.free_cover, solution.form, names, notation);
+        ui.label(RichText::new(format!("Hazard-free: {text}")).monospace());
+    });
+}
@@ -0,0 +1,189 @@
+use egui::{Rect, RichText, Stroke, StrokeKind};
+
+use crate::logic::{
+    kmap::KMap,
+    notation::{self, Notation},
+    practice::{self, GroupCheck},
+    solver::{Form, Solution},
+};
+
+use super::{grid::GridResponse, loops};
+
+/// Groups drawn by hand while practicing
+#[derive(Clone, Debug, Default)]
+pub struct PracticeState {
+    pub enabled: bool,
+    /// Minterms of each group, in drawing order
+    pub groups: Vec<Vec<u32>>,
+    /// Show the comparison with the minimal cover
+    pub checked: bool,
+    /// Output and form the groups belong to, they are dropped when either changes
+    target: Option<(usize, Form)>,
+    /// Cell where the current drag started
+    drag_start: Option<(usize, usize)>,
+}
+
+impl PracticeState {
+    /// Drops the groups when the map switches output or form
+    pub fn retarget(&mut self, output: usize, form: Form) {
+        if self.target != Some((output, form)) {
+            self.target = Some((output, form));
+            self.groups.clear();
+            self.checked = false;
+        }
+    }
+
+    fn push(&mut self, cells: Vec<u32>, extend: bool) {
+        match self.groups.last_mut() {
+            Some(group) if extend => {
+                group.extend(cells);
+                group.sort_unstable();
+                group.dedup();
+            }
+            _ => self.groups.push(cells),
+        }
+        self.checked = false;
+    }
+}
+
+/// Turns clicks and drags on the grid into groups
+///
+/// A drag selects the rectangle between its first and last cell and a
+/// click selects one cell. Holding Shift adds to the latest group instead,
+/// which is how groups that wrap around the edge are built.
+pub fn handle_input(ui: &egui::Ui, state: &mut PracticeState, grid: &GridResponse, kmap: &KMap) {
+    let response = &grid.response;
+    let layout = &grid.layout;
+    let extend = ui.input(|i| i.modifiers.shift);
+
+    if response.drag_started() {
+        state.drag_start = ui
+            .input(|i| i.pointer.press_origin())
+            .and_then(|pos| layout.cell_at(pos));
+    }
+
+    if let Some(start) = state.drag_start {
+        let end = response
+            .interact_pointer_pos()
+            .map(|pos| layout.nearest_cell(pos))
+            .unwrap_or(start);
+
+        if response.drag_stopped() {
+            state.drag_start = None;
+            state.push(cells(kmap, start, end), extend);
+        } else {
+            let rect = layout
+                .cell_rect(start.0.min(end.0), start.1.min(end.1))
+                .union(layout.cell_rect(start.0.max(end.0), start.1.max(end.1)));
+            let stroke = Stroke::new(2.0, ui.visuals().selection.bg_fill);
+            ui.painter()
+                .rect_stroke(rect.shrink(2.0), 4.0, stroke, StrokeKind::Inside);
+        }
+    } else if let Some(minterm) = grid.clicked {
+        state.push(vec![minterm], extend);
+    }
+}
+
+/// Minterms of the cells in the rectangle spanned by two corners
+fn cells(kmap: &KMap, a: (usize, usize), b: (usize, usize)) -> Vec<u32> {
+    let mut out = Vec::new();
+    for row in a.0.min(b.0)..=a.0.max(b.0) {
+        for col in a.1.min(b.1)..=a.1.max(b.1) {
+            out.push(kmap.minterm(row, col));
+        }
+    }
+    out.sort_unstable();
+    out
+}
+
+/// Draws valid groups as loops and tints the cells of invalid ones
+pub fn paint(ui: &egui::Ui, grid: &GridResponse, kmap: &KMap, checks: &[GroupCheck]) {
+    let valid: Vec<_> = checks
+        .iter()
+        .enumerate()
+        .filter(|(_, c)| c.is_valid())
+        .filter_map(|(i, c)| Some((i, c.implicant?)))
+        .collect();
+    loops::paint(ui, &grid.layout, &kmap.format, &valid, None);
+
+    let error = ui.visuals().error_fg_color;
+    for check in checks.iter().filter(|c| !c.is_valid()) {
+        for &minterm in &check.cells {
+            let (row, col) = kmap.cell(minterm);
+            let rect: Rect = grid.layout.cell_rect(row, col).shrink(4.0);
+            ui.painter().rect(
+                rect,
+                2.0,
+                error.gamma_multiply(0.2),
+                Stroke::new(1.5, error),
+                StrokeKind::Inside,
+            );
+        }
+    }
+}
+
+/// Group list with per-group findings, and the review once checked
+pub fn panel(
+    ui: &mut egui::Ui,
+    state: &mut PracticeState,
+    checks: &[GroupCheck],
+    solution: &Solution,
+    names: &[String],
+    notation: Notation,
+) {
+    ui.vertical(|ui| {
+        ui.weak("Drag over cells to draw a group, hold Shift to add to the last one");
+        ui.add_space(4.0);
+
+        let mut remove = None;
+        for (i, check) in checks.iter().enumerate() {
+            ui.horizontal(|ui| {
+                if ui.small_button("×").clicked() {
+                    remove = Some(i);
+                }
+                let term = check.implicant.filter(|_| check.is_valid()).map_or_else(
+                    || "invalid".to_string(),
+                    |t| notation::format_terms(&[t], solution.form, names, notation),
+                );
+                ui.label(RichText::new(format!("Group {}: {term}", i + 1)).color(loops::color(i)));
+                if check.issues.is_empty() {
+                    ui.weak("ok");
+                }
+            });
+            for issue in &check.issues {
+                ui.colored_label(
+                    ui.visuals().warn_fg_color,
+                    format!("    {}", issue.describe(solution.form, names, notation)),
+                );
+            }
+        }
+        if let Some(i) = remove {
+            state.groups.remove(i);
+            state.checked = false;
+        }
+
+        ui.add_space(4.0);
+        ui.horizontal(|ui| {
+            if ui.button("Check").clicked() {
+                state.checked = true;
+            }
+            if ui.button("Clear").clicked() {
+                state.groups.clear();
+                state.checked = false;
+            }
+        });
+
+        if state.checked {
+            ui.add_space(4.0);
+            let review = practice::review(&state.groups, solution);
+            let color = if review.is_minimal() {
+                ui.visuals().strong_text_color()
+            } else {
+                ui.visuals().warn_fg_color
+            };
+            for line in review.messages(names, notation) {
+                ui.colored_label(color, line);
+            }
+        }
+    });
+}
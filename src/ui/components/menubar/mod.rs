@@ -6,6 +6,8 @@ use egui::{
 };
 use egui_extras::{Size, StripBuilder};
 
+use crate::{logic::notation::Notation, project, ui::events::EventQueue};
+
 pub enum MenuEvent {
     NewProject,
     SaveProject(Option<PathBuf>),
@@ -19,6 +21,8 @@ pub struct WindowState {
     pub table_view: bool,
     pub map_view: bool,
     pub expression_view: bool,
+    pub history_view: bool,
+    pub tableau_view: bool,
 }
 
 impl Default for WindowState {
@@ -27,20 +31,60 @@ impl Default for WindowState {
             table_view: true,
             map_view: true,
             expression_view: false,
+            history_view: false,
+            tableau_view: false,
         }
     }
 }
 
-pub fn file_menu(ui: &mut egui::Ui) {
+#[derive(Clone, Debug, Default)]
+pub struct Preferences {
+    pub notation: Notation,
+}
+
+impl Preferences {
+    const NOTATION_KEY: &str = "notation";
+
+    /// Preferences saved by an earlier session, defaults for anything missing
+    pub fn load(storage: Option<&dyn eframe::Storage>) -> Self {
+        let notation = storage
+            .and_then(|s| s.get_string(Self::NOTATION_KEY))
+            .and_then(|name| Notation::ALL.into_iter().find(|n| n.to_string() == name))
+            .unwrap_or_default();
+        Self { notation }
+    }
+
+    pub fn save(&self, storage: &mut dyn eframe::Storage) {
+        storage.set_string(Self::NOTATION_KEY, self.notation.to_string());
+    }
+}
+
+pub fn file_menu(ui: &mut egui::Ui, events: &mut EventQueue) {
+    let dialog = || rfd::FileDialog::new().add_filter("Karny project", &[project::EXTENSION]);
+
     ui.menu_button("File", |ui| {
         ui.label("Project");
         ui.separator();
 
-        if ui.button("New Project").clicked() {}
+        if ui.button("New Project").clicked() {
+            events.push_menu(MenuEvent::NewProject);
+        }
 
-        if ui.button("Open Project").clicked() {}
+        if ui.button("Open Project").clicked()
+            && let Some(path) = dialog().pick_file()
+        {
+            events.push_menu(MenuEvent::OpenProject(path));
+        }
+
+        if ui.button("Save Project").clicked() {
+            events.push_menu(MenuEvent::SaveProject(None));
+        }
 
-        if ui.button("Save Project").clicked() {}
+        if ui.button("Save Project As").clicked()
+            && let Some(path) = dialog().save_file()
+        {
+            events.push_menu(MenuEvent::SaveProject(Some(path)));
+        }
 
         ui.add_space(8.0);
         ui.label("Window");
@@ -58,18 +102,31 @@ pub fn window_menu(ui: &mut egui::Ui, state: &mut WindowState) {
             ui.checkbox(&mut state.table_view, "Table View");
             ui.checkbox(&mut state.map_view, "Map View");
             ui.checkbox(&mut state.expression_view, "Expression View");
+            ui.checkbox(&mut state.history_view, "History");
+            ui.checkbox(&mut state.tableau_view, "Quine–McCluskey");
         });
 }
 
-pub fn preference_menu(ui: &mut egui::Ui) {
+pub fn preference_menu(ui: &mut egui::Ui, preferences: &mut Preferences) {
     ui.menu_button("Preferences", |ui| {
+        ui.menu_button("Notation", |ui| {
+            for notation in Notation::ALL {
+                ui.radio_value(&mut preferences.notation, notation, notation.to_string());
+            }
+        });
+
         if ui.button("Settings").clicked() {
             println!("hi");
         }
     });
 }
 
-pub fn update(ctx: &egui::Context, window_state: &mut WindowState) {
+pub fn update(
+    ctx: &egui::Context,
+    window_state: &mut WindowState,
+    preferences: &mut Preferences,
+    events: &mut EventQueue,
+) {
     TopBottomPanel::top("top_panel").show(ctx, |ui| {
         egui::MenuBar::new().ui(ui, |ui| {
             let full_width = ui.available_width();
@@ -85,9 +142,9 @@ pub fn update(ctx: &egui::Context, window_state: &mut WindowState) {
                     // Left
                     strip.cell(|ui| {
                         ui.horizontal(|ui| {
-                            file_menu(ui);
+                            file_menu(ui, events);
                             window_menu(ui, window_state);
-                            preference_menu(ui);
+                            preference_menu(ui, preferences);
                         });
                     });
 
@@ -114,3 +171,30 @@ pub fn update(ctx: &egui::Context, window_state: &mut WindowState) {
         });
     });
 }
+
+#[test]
+fn restores_the_saved_notation() {
+    #[derive(Default)]
+    struct Memory(std::collections::HashMap<String, String>);
+
+    impl eframe::Storage for Memory {
+        fn get_string(&self, key: &str) -> Option<String> {
+            self.0.get(key).cloned()
+        }
+
+        fn set_string(&mut self, key: &str, value: String) {
+            self.0.insert(key.to_string(), value);
+        }
+
+        fn flush(&mut self) {}
+    }
+
+    let mut storage = Memory::default();
+    assert_eq!(Preferences::load(Some(&storage)).notation, Notation::Prime);
+
+    for notation in Notation::ALL {
+        Preferences { notation }.save(&mut storage);
+        assert_eq!(Preferences::load(Some(&storage)).notation, notation);
+    }
+    assert_eq!(Preferences::load(None).notation, Notation::Prime);
+}
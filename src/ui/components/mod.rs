@@ -5,7 +5,10 @@ pub mod textfield;
 
 pub mod menubar;
 
+pub mod expression_view;
+pub mod history_view;
 pub mod properties_view;
+pub mod tableau_view;
 pub mod variable_view;
 
 pub mod map_view;
@@ -1,5 +1,228 @@
-use crate::app::AppState;
+use egui::{ComboBox, DragValue, Grid, RichText, TextEdit};
 
+use crate::{
+    app::AppState,
+    logic::{
+        notation::{self, Notation},
+        solver::{Form, ImplicantRole, Solution},
+        truth_table::TruthTable,
+        variable::{BitValue, VariableKind},
+    },
+    ui::{
+        components::textfield::rename_field,
+        events::{EventQueue, TableEvent, VariableEvent},
+        selection::Selection,
+        variable::VariableId,
+    },
+};
+
+const VALUES: [BitValue; 3] = [BitValue::Zero, BitValue::One, BitValue::DontCare];
+
+/// Details of the selected variable or truth table cell
 pub fn render(ui: &mut egui::Ui, app_state: &mut AppState) {
     ui.label("Properties");
+    ui.separator();
+
+    match app_state.selection {
+        Selection::None => {
+            ui.weak("Select a variable, table row or map cell");
+        }
+        Selection::Variable(id) => variable(ui, app_state, id),
+        Selection::Cell { row, output } => cell(ui, app_state, row, output),
+    }
+}
+
+fn variable(ui: &mut egui::Ui, app_state: &mut AppState, id: VariableId) {
+    let events = &mut app_state.events;
+    let table = &app_state.table;
+    let (Some(var), Some((kind, index))) = (
+        app_state.variables.get_variable_by_id(id),
+        app_state.variables.position(id),
+    ) else {
+        app_state.selection = Selection::None;
+        return;
+    };
+    let count = match kind {
+        VariableKind::Input => app_state.variables.inputs.len(),
+        VariableKind::Output => app_state.variables.outputs.len(),
+    };
+
+    Grid::new("variable_properties")
+        .num_columns(2)
+        .show(ui, |ui| {
+            ui.label("Name");
+            let rename = rename_field(
+                ui,
+                ui.id().with(("variable_name", id)),
+                &var.name,
+                |text| TextEdit::singleline(text),
+                |name| table.check_name(name, Some(&var.name)),
+            );
+            if let Some(name) = rename.name {
+                events.push_variable(VariableEvent::Rename(id, name));
+            }
+            ui.end_row();
+            if let Some(err) = rename.error {
+                ui.label("");
+                ui.colored_label(ui.visuals().error_fg_color, err.to_string());
+                ui.end_row();
+            }
+
+            ui.label("Kind");
+            ui.label(kind.to_string());
+            ui.end_row();
+
+            ui.label("Position");
+            let mut position = index;
+            ui.add(DragValue::new(&mut position).range(0..=count - 1));
+            if position != index {
+                // Moving down lands before the item after the target
+                let to = if position > index {
+                    position + 1
+                } else {
+                    position
+                };
+                events.push_variable(VariableEvent::Move {
+                    kind,
+                    from: index,
+                    to,
+                });
+            }
+            ui.end_row();
+
+            ui.label("Initial value");
+            let mut value = var.value;
+            value_combo(ui, ("variable_value", id), &mut value);
+            if value != var.value {
+                events.push_variable(VariableEvent::SetValue(id, value));
+            }
+            ui.end_row();
+        });
+
+    ui.add_space(8.0);
+    if ui.button(format!("Remove {}", var.name)).clicked() {
+        events.push_variable(VariableEvent::Remove(id));
+    }
+}
+
+fn cell(ui: &mut egui::Ui, app_state: &mut AppState, row: usize, output: Option<usize>) {
+    let table = &app_state.table;
+    if row >= table.rows.len() || output.is_some_and(|o| o >= table.outputs.len()) {
+        app_state.selection = Selection::None;
+        return;
+    }
+
+    let n = table.inputs.len();
+    Grid::new("cell_properties").num_columns(2).show(ui, |ui| {
+        ui.label("Minterm");
+        ui.label(format!("m{row}"));
+        ui.end_row();
+
+        ui.label("Binary");
+        ui.monospace(format!("{row:0n$b}", n = n.max(1)));
+        ui.end_row();
+
+        ui.label("Decimal");
+        ui.monospace(row.to_string());
+        ui.end_row();
+
+        ui.label("Hex");
+        ui.monospace(format!("0x{row:X}"));
+        ui.end_row();
+
+        for (name, value) in table.inputs.iter().zip(&table.rows[row].inputs) {
+            ui.label(name);
+            ui.monospace(value.to_string());
+            ui.end_row();
+        }
+    });
+
+    let outputs = match output {
+        Some(o) => o..o + 1,
+        None => 0..table.outputs.len(),
+    };
+    let notation = app_state.preferences.notation;
+    for o in outputs {
+        ui.separator();
+        let Some(solutions) = app_state.solutions.both(table, o) else {
+            ui.weak(format!("Solving {}…", table.outputs[o]));
+            continue;
+        };
+        output_cell(
+            ui,
+            table,
+            row,
+            o,
+            solutions,
+            notation,
+            &mut app_state.events,
+        );
+    }
+}
+
+/// Value of one output in the selected row and the implicants covering it
+fn output_cell(
+    ui: &mut egui::Ui,
+    table: &TruthTable,
+    row: usize,
+    output: usize,
+    solutions: [&Solution; 2],
+    notation: Notation,
+    events: &mut EventQueue,
+) {
+    let current = table.rows[row].outputs[output];
+    ui.horizontal(|ui| {
+        ui.strong(&table.outputs[output]);
+        let mut value = current;
+        value_combo(ui, ("cell_value", output), &mut value);
+        if value != current {
+            events.push_table(TableEvent::Set { row, output, value });
+        }
+    });
+
+    for solution in solutions {
+        // Ones are covered by SOP terms and zeros by POS terms, don't cares
+        // may be picked up by either
+        if current != BitValue::DontCare && current != solution.form.target() {
+            continue;
+        }
+
+        let covering: Vec<_> = solution
+            .roles()
+            .into_iter()
+            .filter(|(term, _)| term.covers(row as u32))
+            .collect();
+        let form = match solution.form {
+            Form::Sop => "SOP",
+            Form::Pos => "POS",
+        };
+        if covering.is_empty() {
+            ui.weak(format!("No {form} implicant covers this cell"));
+            continue;
+        }
+
+        ui.label(format!("{form} implicants"));
+        for (term, role) in covering {
+            let text = notation::format_terms(&[term], solution.form, &table.inputs, notation);
+            let role = match role {
+                ImplicantRole::Essential => "essential",
+                ImplicantRole::Selected => "selected",
+                ImplicantRole::Redundant => "redundant",
+            };
+            ui.horizontal(|ui| {
+                ui.monospace(text);
+                ui.weak(role);
+            });
+        }
+    }
+}
+
+fn value_combo(ui: &mut egui::Ui, id: impl std::hash::Hash, value: &mut BitValue) {
+    ComboBox::from_id_salt(id)
+        .selected_text(RichText::new(value.to_string()).monospace())
+        .show_ui(ui, |ui| {
+            for option in VALUES {
+                ui.selectable_value(value, option, option.to_string());
+            }
+        });
 }
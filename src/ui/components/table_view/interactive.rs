@@ -1,14 +1,148 @@
-use egui::{Direction, Frame, Layout, Margin};
+use egui::{Align, Button, Frame, Label, Layout, Margin, RichText, Sense};
+use egui_extras::{Column, TableBuilder};
 
-pub fn render(ui: &mut egui::Ui) {
+use crate::{
+    logic::{
+        canonical::{Canonical, TermKind},
+        truth_table::TruthTable,
+        variable::BitValue,
+    },
+    ui::{
+        events::{EventQueue, TableEvent},
+        selection::{HoveredTerm, Selection},
+    },
+};
+
+const ROW_HEIGHT: f32 = 20.0;
+
+/// Editable grid of every row, clicking an output cell cycles its value and
+/// right-clicking an output header fills or copies the column
+///
+/// Clicking a row number selects the row, clicking an output cell also
+/// selects that cell. Rows inside the loop hovered on the map are
+/// highlighted.
+///
+/// Rows are laid out lazily by [`egui_extras::TableBody::rows`] so large
+/// tables only pay for the rows in view.
+pub fn render(
+    ui: &mut egui::Ui,
+    table: &TruthTable,
+    selection: &mut Selection,
+    hovered: Option<HoveredTerm>,
+    events: &mut EventQueue,
+) {
     egui::CentralPanel::default()
         .frame(Frame::default().inner_margin(Margin::same(6)))
         .show_inside(ui, |ui| {
-            ui.with_layout(
-                Layout::centered_and_justified(Direction::LeftToRight),
-                |ui| {
-                    ui.label("test");
-                },
-            );
+            if table.inputs.is_empty() && table.outputs.is_empty() {
+                ui.vertical_centered(|ui| {
+                    ui.weak("Add variables to fill the table");
+                });
+                return;
+            }
+
+            let cell_layout = Layout::centered_and_justified(egui::Direction::LeftToRight);
+
+            TableBuilder::new(ui)
+                .id_salt("truth_table")
+                .striped(true)
+                .sense(Sense::click())
+                .cell_layout(Layout::left_to_right(Align::Center))
+                .auto_shrink([false, false])
+                .column(Column::auto().at_least(32.0))
+                .columns(
+                    Column::auto().at_least(24.0),
+                    table.inputs.len() + table.outputs.len(),
+                )
+                .header(ROW_HEIGHT, |mut header| {
+                    header.col(|ui| {
+                        ui.weak("#");
+                    });
+                    for name in &table.inputs {
+                        header.col(|ui| {
+                            ui.label(name);
+                        });
+                    }
+                    for (output, name) in table.outputs.iter().enumerate() {
+                        header.col(|ui| {
+                            let label =
+                                Label::new(RichText::new(name).strong()).sense(Sense::click());
+                            ui.add(label).context_menu(|ui| {
+                                for value in [BitValue::Zero, BitValue::One, BitValue::DontCare] {
+                                    if ui.button(format!("Fill with {value}")).clicked() {
+                                        events.push_table(TableEvent::Fill { output, value });
+                                    }
+                                }
+                                ui.separator();
+                                for (text, kind) in [
+                                    ("Copy as Σm", TermKind::Minterms),
+                                    ("Copy as ΠM", TermKind::Maxterms),
+                                ] {
+                                    if ui.button(text).clicked() {
+                                        let canonical = Canonical::from_table(table, output, kind);
+                                        ui.ctx().copy_text(canonical.to_string());
+                                    }
+                                }
+                            });
+                        });
+                    }
+                })
+                .body(|body| {
+                    body.rows(ROW_HEIGHT, table.rows.len(), |mut row| {
+                        let index = row.index();
+                        let truth_row = &table.rows[index];
+                        row.set_selected(
+                            matches!(*selection, Selection::Cell { row, .. } if row == index),
+                        );
+                        let looped = hovered
+                            .filter(|h| h.implicant.covers(index as u32))
+                            .map(|h| h.output);
+                        row.set_hovered(looped.is_some());
+
+                        let (_, number) = row.col(|ui| {
+                            ui.weak(index.to_string());
+                        });
+                        if number.clicked() {
+                            *selection = Selection::Cell {
+                                row: index,
+                                output: None,
+                            };
+                        }
+                        for value in &truth_row.inputs {
+                            row.col(|ui| {
+                                ui.with_layout(cell_layout, |ui| {
+                                    ui.label(value.to_string());
+                                });
+                            });
+                        }
+                        for (output, value) in truth_row.outputs.iter().enumerate() {
+                            let conflict = table.conflicts.contains(&(index, output));
+                            row.col(|ui| {
+                                if conflict {
+                                    let tint = ui.visuals().error_fg_color.gamma_multiply(0.25);
+                                    ui.painter().rect_filled(ui.max_rect(), 0.0, tint);
+                                } else if looped == Some(output) {
+                                    let tint = ui.visuals().selection.bg_fill.gamma_multiply(0.5);
+                                    ui.painter().rect_filled(ui.max_rect(), 0.0, tint);
+                                }
+
+                                let button = Button::new(RichText::new(value.to_string()).strong())
+                                    .frame(false);
+                                let mut response = ui.add_sized(ui.available_size(), button);
+                                if conflict {
+                                    response = response
+                                        .on_hover_text("Merged rows disagreed, pick a value");
+                                }
+                                if response.clicked() {
+                                    *selection = Selection::Cell {
+                                        row: index,
+                                        output: Some(output),
+                                    };
+                                    events.push_table(TableEvent::Toggle { row: index, output });
+                                }
+                            });
+                        }
+                    });
+                });
         });
 }
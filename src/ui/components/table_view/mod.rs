@@ -1,10 +1,22 @@
-use crate::logic::truth_table::TruthTable;
+use crate::{
+    logic::truth_table::TruthTable,
+    ui::{
+        events::EventQueue,
+        selection::{HoveredTerm, Selection},
+    },
+};
 use egui::{Frame, Margin, Vec2};
 
 mod interactive;
 mod simple;
 
-pub fn render(ui: &mut egui::Ui, table: &mut TruthTable) {
+pub fn render(
+    ui: &mut egui::Ui,
+    table: &TruthTable,
+    selection: &mut Selection,
+    hovered: Option<HoveredTerm>,
+    events: &mut EventQueue,
+) {
     ui.spacing_mut().item_spacing = Vec2::ZERO;
 
     ui.add_space(8.0);
@@ -17,6 +29,6 @@ pub fn render(ui: &mut egui::Ui, table: &mut TruthTable) {
         ui.set_min_size(Vec2::new(ui.available_width(), ui.available_height()));
 
         simple::render(ui, table);
-        interactive::render(ui);
+        interactive::render(ui, table, selection, hovered, events);
     });
 }
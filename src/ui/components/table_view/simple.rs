@@ -1,15 +1,59 @@
-use crate::logic::truth_table::TruthTable;
+use egui::{RichText, TextStyle};
 
-pub fn render(ui: &mut egui::Ui, _table: &TruthTable) {
+use crate::logic::{
+    truth_table::{TruthRow, TruthTable},
+    variable::BitValue,
+};
+
+/// Compact read-only listing with one line of bits per row
+pub fn render(ui: &mut egui::Ui, table: &TruthTable) {
     egui::TopBottomPanel::bottom("table_bottom")
         .resizable(true)
         .show_inside(ui, |ui| {
+            let index_width = table.rows.len().saturating_sub(1).to_string().len();
+            let row_height = ui.text_style_height(&TextStyle::Monospace);
+            let widths = |names: &[String]| -> Vec<usize> {
+                names.iter().map(|n| n.chars().count().max(1)).collect()
+            };
+            let (input_widths, output_widths) = (widths(&table.inputs), widths(&table.outputs));
+
+            ui.label(
+                RichText::new(format!(
+                    "{:>index_width$}  {} | {}",
+                    "#",
+                    table.inputs.join(" "),
+                    table.outputs.join(" ")
+                ))
+                .monospace()
+                .strong(),
+            );
+
             egui::ScrollArea::vertical()
                 .auto_shrink([false, false])
-                .show(ui, |ui| {
-                    ui.label("row");
-                    ui.label("row2");
-                    ui.label("row3");
+                .show_rows(ui, row_height, table.rows.len(), |ui, range| {
+                    for index in range {
+                        ui.monospace(format!(
+                            "{index:>index_width$}  {}",
+                            line(&table.rows[index], &input_widths, &output_widths)
+                        ));
+                    }
                 });
         });
 }
+
+/// Formats a row with each bit right-aligned under its column name
+fn line(row: &TruthRow, input_widths: &[usize], output_widths: &[usize]) -> String {
+    let bits = |values: &[BitValue], widths: &[usize]| {
+        values
+            .iter()
+            .zip(widths)
+            .map(|(value, width)| format!("{value:>width$}"))
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+    format!(
+        "{} | {}",
+        bits(&row.inputs, input_widths),
+        bits(&row.outputs, output_widths)
+    )
+}
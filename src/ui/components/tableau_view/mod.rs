@@ -0,0 +1,286 @@
+use egui::{Button, ComboBox, Grid, RichText};
+
+use crate::{
+    app::AppState,
+    logic::{
+        notation::{self, Notation},
+        solver::{
+            Form, Implicant, Method,
+            tableau::{MAX_INPUTS, Round, Stage, Tableau},
+        },
+    },
+};
+
+#[derive(Clone, Debug, Default)]
+pub struct TableauViewState {
+    /// Index of the output being walked through
+    pub output: usize,
+    pub form: Form,
+    /// Index into [`Tableau::stages`]
+    pub stage: usize,
+    /// Rebuilt whenever the function it was built from changes
+    tableau: Option<Tableau>,
+}
+
+/// Floating window stepping through Quine–McCluskey for one output
+pub fn update(ctx: &egui::Context, app_state: &mut AppState) {
+    let mut open = app_state.window_state.tableau_view;
+    egui::Window::new("Quine–McCluskey")
+        .open(&mut open)
+        .default_width(420.0)
+        .show(ctx, |ui| render(ui, app_state));
+    app_state.window_state.tableau_view = open;
+}
+
+fn render(ui: &mut egui::Ui, app_state: &mut AppState) {
+    let table = &app_state.table;
+    let state = &mut app_state.tableau;
+    let notation = app_state.preferences.notation;
+
+    if table.outputs.is_empty() {
+        ui.weak("Add an output variable to step through its minimization");
+        return;
+    }
+    if table.inputs.len() > MAX_INPUTS {
+        ui.weak(format!(
+            "The tableau is only shown for up to {MAX_INPUTS} inputs"
+        ));
+        return;
+    }
+    state.output = state.output.min(table.outputs.len() - 1);
+
+    ui.horizontal(|ui| {
+        if table.outputs.len() > 1 {
+            ComboBox::from_id_salt("tableau_output")
+                .selected_text(&table.outputs[state.output])
+                .show_ui(ui, |ui| {
+                    for (i, name) in table.outputs.iter().enumerate() {
+                        ui.selectable_value(&mut state.output, i, name);
+                    }
+                });
+        }
+        ComboBox::from_id_salt("tableau_form")
+            .selected_text(form_label(state.form))
+            .show_ui(ui, |ui| {
+                for form in [Form::Sop, Form::Pos] {
+                    ui.selectable_value(&mut state.form, form, form_label(form));
+                }
+            });
+    });
+
+    let Some(solution) = app_state
+        .solutions
+        .solution(table, state.output, state.form)
+    else {
+        ui.weak("Solving…");
+        return;
+    };
+    if !state.tableau.as_ref().is_some_and(|t| t.matches(solution)) {
+        state.tableau = solution.tableau();
+    }
+    let Some(tableau) = &state.tableau else {
+        return;
+    };
+
+    let stages = tableau.stages();
+    state.stage = state.stage.min(stages.len() - 1);
+    ui.horizontal(|ui| {
+        if ui
+            .add_enabled(state.stage > 0, Button::new("◀ Previous"))
+            .clicked()
+        {
+            state.stage -= 1;
+        }
+        if ui
+            .add_enabled(state.stage + 1 < stages.len(), Button::new("Next ▶"))
+            .clicked()
+        {
+            state.stage += 1;
+        }
+        ui.label(format!("Step {} of {}", state.stage + 1, stages.len()));
+    });
+    ui.separator();
+
+    let names = &table.inputs;
+    egui::ScrollArea::both()
+        .auto_shrink([false, true])
+        .show(ui, |ui| match stages[state.stage] {
+            Stage::Round(i) => round(ui, tableau, i),
+            Stage::Primes => primes(ui, tableau, names, notation),
+            Stage::Chart => chart(ui, tableau, names, notation),
+        });
+}
+
+fn form_label(form: Form) -> &'static str {
+    match form {
+        Form::Sop => "SOP",
+        Form::Pos => "POS",
+    }
+}
+
+/// The cells a term stands for, as `m(0, 2)` or `M(0, 2)`
+fn cells(tableau: &Tableau, term: &Implicant) -> String {
+    let prefix = match tableau.form {
+        Form::Sop => 'm',
+        Form::Pos => 'M',
+    };
+    let list: Vec<String> = term.minterms().iter().map(u32::to_string).collect();
+    format!("{prefix}({})", list.join(", "))
+}
+
+/// A term made up only of don't cares, drawn faded since it never has to be covered
+fn only_dont_cares(tableau: &Tableau, term: &Implicant) -> bool {
+    !tableau.minterms.iter().any(|&m| term.covers(m))
+}
+
+fn round(ui: &mut egui::Ui, tableau: &Tableau, index: usize) {
+    let cells_name = match tableau.form {
+        Form::Sop => "minterm",
+        Form::Pos => "maxterm",
+    };
+    if index == 0 {
+        ui.strong(format!("Group each {cells_name} by its number of 1s"));
+        ui.weak("Don't cares take part in merging but never need covering");
+    } else {
+        ui.strong(format!("Merge round {index}"));
+        ui.weak("Terms one group apart that differ in a single bit combine, the bit becomes -");
+    }
+    ui.weak("Checked terms merged into the next round, the rest are prime");
+    ui.add_space(4.0);
+
+    let Round { groups } = &tableau.rounds[index];
+    Grid::new(("tableau_round", index))
+        .num_columns(4)
+        .striped(true)
+        .show(ui, |ui| {
+            ui.strong("1s");
+            ui.strong("Cells");
+            ui.strong("Pattern");
+            ui.strong("");
+            ui.end_row();
+
+            for group in groups {
+                for (i, entry) in group.terms.iter().enumerate() {
+                    if i == 0 {
+                        ui.label(group.ones.to_string());
+                    } else {
+                        ui.label("");
+                    }
+
+                    let text = RichText::new(cells(tableau, &entry.implicant));
+                    if only_dont_cares(tableau, &entry.implicant) {
+                        ui.label(text.weak());
+                    } else {
+                        ui.label(text);
+                    }
+                    ui.monospace(entry.implicant.pattern(tableau.variables));
+                    ui.label(if entry.merged { "✓" } else { "" });
+                    ui.end_row();
+                }
+            }
+        });
+
+    if tableau.rounds[index].groups.is_empty() {
+        ui.weak(format!("There are no {cells_name}s to group"));
+    }
+}
+
+fn primes(ui: &mut egui::Ui, tableau: &Tableau, names: &[String], notation: Notation) {
+    ui.strong("Prime implicants");
+    ui.weak("Every unchecked term, except those covering only don't cares");
+    ui.add_space(4.0);
+
+    if tableau.primes.is_empty() {
+        ui.weak("The function has no prime implicants");
+        return;
+    }
+
+    Grid::new("tableau_primes")
+        .num_columns(3)
+        .striped(true)
+        .show(ui, |ui| {
+            for prime in &tableau.primes {
+                ui.monospace(prime.pattern(tableau.variables));
+                ui.label(cells(tableau, prime));
+                ui.monospace(notation::format_terms(
+                    &[*prime],
+                    tableau.form,
+                    names,
+                    notation,
+                ));
+                ui.end_row();
+            }
+        });
+}
+
+fn chart(ui: &mut egui::Ui, tableau: &Tableau, names: &[String], notation: Notation) {
+    ui.strong("Prime implicant chart");
+    ui.weak("A column with a single cross makes its row essential");
+    ui.add_space(4.0);
+
+    if tableau.minterms.is_empty() {
+        ui.weak("Nothing to cover");
+        return;
+    }
+
+    let highlight = ui.visuals().selection.bg_fill;
+    let term = |t: &Implicant| notation::format_terms(&[*t], tableau.form, names, notation);
+    Grid::new("tableau_chart")
+        .num_columns(tableau.minterms.len() + 2)
+        .striped(true)
+        .show(ui, |ui| {
+            ui.label("");
+            for m in &tableau.minterms {
+                let text = RichText::new(m.to_string()).monospace();
+                if tableau.essential_columns.contains(m) {
+                    ui.label(text.strong().color(highlight));
+                } else {
+                    ui.label(text);
+                }
+            }
+            ui.label("");
+            ui.end_row();
+
+            for prime in &tableau.primes {
+                let essential = tableau.essential.contains(prime);
+                let text = RichText::new(term(prime)).monospace();
+                ui.label(if essential { text.strong() } else { text });
+
+                for m in &tableau.minterms {
+                    if !prime.covers(*m) {
+                        ui.label("");
+                    } else if tableau.essential_columns.contains(m) {
+                        ui.label(RichText::new("⊗").strong().color(highlight));
+                    } else {
+                        ui.label("×");
+                    }
+                }
+
+                if essential {
+                    ui.weak("essential");
+                } else if tableau.cover.contains(prime) {
+                    ui.weak("selected");
+                } else {
+                    ui.label("");
+                }
+                ui.end_row();
+            }
+        });
+
+    ui.add_space(4.0);
+    if !tableau.essential.is_empty() {
+        let essential: Vec<String> = tableau.essential.iter().map(term).collect();
+        ui.label(format!("Essential: {}", essential.join(", ")));
+    }
+    if tableau.cover.len() > tableau.essential.len() {
+        ui.label(match tableau.method {
+            Method::Heuristic => {
+                "The remaining columns are too many to search, so the heuristic covers \
+                 them and the cover may not be minimal"
+            }
+            _ => "The remaining columns are covered at minimal cost with Petrick's method",
+        });
+    }
+    let cover = notation::format_terms(&tableau.cover, tableau.form, names, notation);
+    ui.label(RichText::new(format!("Cover: {cover}")).monospace());
+}
@@ -1,3 +1,7 @@
+use egui::{Id, TextEdit};
+
+use crate::logic::truth_table::NameError;
+
 pub fn labeled_textfield(
     ui: &mut egui::Ui,
     label: &str,
@@ -6,7 +10,55 @@ pub fn labeled_textfield(
 ) -> egui::Response {
     ui.horizontal(|ui| {
         ui.label(label);
-        ui.add(egui::TextEdit::singleline(text).desired_width(entry_width))
+        ui.add(TextEdit::singleline(text).desired_width(entry_width))
     })
     .response
 }
+
+/// What a rename field reported this frame
+pub struct Rename {
+    /// A new name that passed the check
+    pub name: Option<String>,
+    /// Why the text in the field was rejected
+    pub error: Option<NameError>,
+}
+
+/// Text field renaming a variable, which only reports names `check` accepts
+///
+/// Rejected text stays in the field as a draft in egui's memory, so clearing
+/// the field to type a new name works, and the error is returned for the
+/// caller to show.
+pub fn rename_field(
+    ui: &mut egui::Ui,
+    id: Id,
+    name: &str,
+    edit: impl for<'t> FnOnce(&'t mut String) -> TextEdit<'t>,
+    check: impl FnOnce(&str) -> Result<(), NameError>,
+) -> Rename {
+    let draft: Option<String> = ui.data(|d| d.get_temp(id));
+    let mut text = draft.clone().unwrap_or_else(|| name.to_string());
+    let changed = ui.add(edit(&mut text).id(id)).changed();
+    if !changed && draft.is_none() {
+        return Rename {
+            name: None,
+            error: None,
+        };
+    }
+
+    match check(&text) {
+        Ok(()) => {
+            ui.data_mut(|d| d.remove::<String>(id));
+            Rename {
+                name: (text != name).then_some(text),
+                error: None,
+            }
+        }
+        Err(err) => {
+            ui.data_mut(|d| d.insert_temp(id, text));
+            Rename {
+                name: None,
+                error: Some(err),
+            }
+        }
+    }
+}
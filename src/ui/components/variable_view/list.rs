@@ -1,12 +1,40 @@
 use egui_dnd::dnd;
 
 use super::row;
-use crate::ui::variable::Variable;
+use crate::{
+    logic::{truth_table::TruthTable, variable::VariableKind},
+    ui::{
+        events::{EventQueue, VariableEvent},
+        selection::Selection,
+        variable::Variable,
+    },
+};
 
-pub fn render(ui: &mut egui::Ui, variables: &mut Vec<Variable>, id: &'static str) {
+pub fn render(
+    ui: &mut egui::Ui,
+    variables: &mut [Variable],
+    kind: VariableKind,
+    selection: &mut Selection,
+    table: &TruthTable,
+    events: &mut EventQueue,
+    id: &'static str,
+) {
     egui::ScrollArea::vertical().show(ui, |ui| {
-        dnd(ui, id).show_vec(variables, |ui, var, handle, item_state| {
-            row::render(ui, var, handle, item_state.index.to_string());
+        // Reordering goes through the event queue so the truth table follows
+        let response = dnd(ui, id).show(variables.iter_mut(), |ui, var, handle, item_state| {
+            let selected = *selection == Selection::Variable(var.id);
+            let index = item_state.index.to_string();
+            if row::render(ui, var, handle, index, selected, table, events) {
+                *selection = Selection::Variable(var.id);
+            }
         });
+
+        if let Some(update) = response.final_update() {
+            events.push_variable(VariableEvent::Move {
+                kind,
+                from: update.from,
+                to: update.to,
+            });
+        }
     });
 }
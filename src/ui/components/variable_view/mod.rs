@@ -1,4 +1,4 @@
-use crate::app::AppState;
+use crate::{app::AppState, logic::variable::VariableKind};
 
 mod list;
 mod row;
@@ -10,11 +10,27 @@ pub fn render(ui: &mut egui::Ui, app_state: &mut AppState) {
         }
 
         ui.collapsing("Inputs", |ui| {
-            list::render(ui, &mut app_state.variables.inputs, "inputs");
+            list::render(
+                ui,
+                &mut app_state.variables.inputs,
+                VariableKind::Input,
+                &mut app_state.selection,
+                &app_state.table,
+                &mut app_state.events,
+                "inputs",
+            );
         });
 
         ui.collapsing("Outputs", |ui| {
-            list::render(ui, &mut app_state.variables.outputs, "outputs");
+            list::render(
+                ui,
+                &mut app_state.variables.outputs,
+                VariableKind::Output,
+                &mut app_state.selection,
+                &app_state.table,
+                &mut app_state.events,
+                "outputs",
+            );
         });
     });
 }
@@ -1,18 +1,38 @@
-use crate::ui::variable::Variable;
-use egui::{Margin, Stroke, TextEdit};
+use crate::{
+    logic::truth_table::TruthTable,
+    ui::{
+        components::textfield::rename_field,
+        events::{EventQueue, VariableEvent},
+        variable::Variable,
+    },
+};
+use egui::{Margin, Sense, Stroke, TextEdit};
 use egui_dnd::Handle;
 
-pub fn render(ui: &mut egui::Ui, variable: &mut Variable, handle: Handle, index: String) {
+/// Draws one variable, returning whether its background was clicked
+pub fn render(
+    ui: &mut egui::Ui,
+    variable: &mut Variable,
+    handle: Handle,
+    index: String,
+    selected: bool,
+    table: &TruthTable,
+    events: &mut EventQueue,
+) -> bool {
+    let border = if selected {
+        ui.visuals().selection.bg_fill
+    } else {
+        ui.visuals().widgets.noninteractive.bg_stroke.color
+    };
+
     ui.push_id(variable.id, |ui| {
         egui::Frame::group(ui.style())
             .fill(ui.visuals().extreme_bg_color)
-            .stroke(Stroke::new(
-                1.0,
-                ui.visuals().widgets.noninteractive.bg_stroke.color,
-            ))
+            .stroke(Stroke::new(1.0, border))
             .corner_radius(2.0) // rounded corners
             .inner_margin(Margin::symmetric(8, 4))
             .show(ui, |ui| {
+                let mut error = None;
                 ui.horizontal(|ui| {
                     ui.take_available_space();
 
@@ -21,12 +41,24 @@ pub fn render(ui: &mut egui::Ui, variable: &mut Variable, handle: Handle, index:
                     });
 
                     ui.separator();
-                    ui.add(
-                        TextEdit::singleline(&mut variable.name)
-                            .char_limit(16)
-                            .desired_width(ui.available_width() - 40.0)
-                            .id_source("name"),
+
+                    // Renames are applied by the event queue to keep the table in sync
+                    let width = ui.available_width() - 40.0;
+                    let rename = rename_field(
+                        ui,
+                        ui.id().with("name"),
+                        &variable.name,
+                        |text| {
+                            TextEdit::singleline(text)
+                                .char_limit(16)
+                                .desired_width(width)
+                        },
+                        |name| table.check_name(name, Some(&variable.name)),
                     );
+                    if let Some(name) = rename.name {
+                        events.push_variable(VariableEvent::Rename(variable.id, name));
+                    }
+                    error = rename.error;
 
                     ui.add_space(4.0);
 
@@ -37,6 +69,13 @@ pub fn render(ui: &mut egui::Ui, variable: &mut Variable, handle: Handle, index:
 
                     resp
                 });
-            });
-    });
+                if let Some(err) = error {
+                    ui.colored_label(ui.visuals().error_fg_color, err.to_string());
+                }
+            })
+            .response
+            .interact(Sense::click())
+            .clicked()
+    })
+    .inner
 }
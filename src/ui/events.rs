@@ -1,17 +1,231 @@
-use crate::{app::AppState};
+use std::path::PathBuf;
 
-pub use crate::ui::variable::VariableEvent;
+use egui_dnd::utils::shift_vec;
+
+use crate::{
+    app::AppState,
+    logic::variable::{BitValue, VariableKind},
+    project::{self, ProjectFile},
+    ui::history::{self, Merge},
+};
+
+pub use crate::ui::{components::menubar::MenuEvent, variable::VariableEvent};
+
+/// Edits to output cells of the truth table
+pub enum TableEvent {
+    /// Cycles one cell through 0, 1 and don't care
+    Toggle { row: usize, output: usize },
+    /// Sets one cell
+    Set {
+        row: usize,
+        output: usize,
+        value: BitValue,
+    },
+    /// Sets every cell of an output column
+    Fill { output: usize, value: BitValue },
+    /// Replaces an output column, one value per row
+    Assign {
+        output: usize,
+        values: Vec<BitValue>,
+    },
+}
+
+/// History label for a variable event, and whether it merges with the
+/// previous entry
+fn describe_variable_event(state: &AppState, ev: &VariableEvent) -> (String, Option<Merge>) {
+    let name = |id| {
+        state
+            .variables
+            .get_variable_by_id(id)
+            .map_or("variable", |v| v.name.as_str())
+    };
+
+    match ev {
+        VariableEvent::Add { name, kind, .. } => (
+            format!("Add {} {name}", kind.to_string().to_lowercase()),
+            None,
+        ),
+        VariableEvent::Remove(id) => (format!("Remove {}", name(*id)), None),
+        VariableEvent::Rename(id, _) => (format!("Rename {}", name(*id)), Some(Merge::Rename(*id))),
+        VariableEvent::SetValue(id, value) => (format!("Set {} to {value}", name(*id)), None),
+        VariableEvent::Move { kind, .. } => (
+            format!("Reorder {}s", kind.to_string().to_lowercase()),
+            None,
+        ),
+    }
+}
+
+fn describe_table_event(state: &AppState, ev: &TableEvent) -> String {
+    let output = |i: usize| state.table.outputs.get(i).map_or("output", String::as_str);
+
+    match ev {
+        TableEvent::Toggle { row, output: o } | TableEvent::Set { row, output: o, .. } => {
+            format!("Edit {} row {row}", output(*o))
+        }
+        TableEvent::Fill { output: o, value } => format!("Fill {} with {value}", output(*o)),
+        TableEvent::Assign { output: o, .. } => format!("Set {} from expression", output(*o)),
+    }
+}
+
+/// Applies a cell edit, which fails when an earlier event in the same frame
+/// removed its output
+fn handle_table_event(state: &mut AppState, ev: TableEvent) -> Result<(), String> {
+    let table = &mut state.table;
+    let (TableEvent::Toggle { output, .. }
+    | TableEvent::Set { output, .. }
+    | TableEvent::Fill { output, .. }
+    | TableEvent::Assign { output, .. }) = ev;
+    if output >= table.outputs.len() {
+        return Err(format!("Output {output} no longer exists"));
+    }
+
+    match ev {
+        TableEvent::Toggle { row, output } => table.toggle(row, output),
+        TableEvent::Set { row, output, value } => table.set(row, output, value),
+        TableEvent::Fill { output, value } => {
+            for row in 0..table.rows.len() {
+                table.set(row, output, value);
+            }
+        }
+        TableEvent::Assign { output, values } => {
+            for (row, value) in values.into_iter().enumerate() {
+                table.set(row, output, value);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Applies a variable edit to the store and mirrors it in the truth table,
+/// whose inputs and outputs follow the store's order
+///
+/// ### Returns
+/// A message for the error modal when the table can't take the edit
+fn handle_variable_event(state: &mut AppState, ev: VariableEvent) -> Result<(), String> {
+    let table = &mut state.table;
 
-fn handle_variable_event(state: &mut AppState, ev: VariableEvent) {
     match ev {
-        VariableEvent::Add { name, kind, value } => state.variables.add(name, kind, value),
-        VariableEvent::Remove(id) => state.variables.remove(id),
-        VariableEvent::Rename(id, new_name) => state.variables.rename(id, new_name),
+        VariableEvent::Add { name, kind, value } => {
+            match kind {
+                VariableKind::Input => table
+                    .insert_input(table.inputs.len(), name.clone())
+                    .map_err(|err| format!("Could not add input {name}: {err}"))?,
+                VariableKind::Output => table.add_output(name.clone()),
+            }
+            state.variables.add(name, kind, value);
+        }
+        VariableEvent::Remove(id) => {
+            match state.variables.position(id) {
+                Some((VariableKind::Input, index)) => {
+                    table
+                        .remove_input(index)
+                        .map_err(|err| format!("Could not remove input: {err}"))?;
+                }
+                Some((VariableKind::Output, index)) => {
+                    state.map.layouts.remove(&table.outputs[index]);
+                    table.remove_output(index);
+                }
+                None => {}
+            }
+            state.variables.remove(id);
+        }
+        VariableEvent::Rename(id, new_name) => {
+            let old = state
+                .variables
+                .get_variable_by_id(id)
+                .map(|v| v.name.as_str());
+            table
+                .check_name(&new_name, old)
+                .map_err(|err| format!("Could not rename {}: {err}", old.unwrap_or("variable")))?;
+            match state.variables.position(id) {
+                Some((VariableKind::Input, index)) => {
+                    let old = std::mem::replace(&mut table.inputs[index], new_name.clone());
+                    for layout in state.map.layouts.values_mut() {
+                        layout.rename(&old, &new_name);
+                    }
+                }
+                Some((VariableKind::Output, index)) => {
+                    let old = std::mem::replace(&mut table.outputs[index], new_name.clone());
+                    if let Some(layout) = state.map.layouts.remove(&old) {
+                        state.map.layouts.insert(new_name.clone(), layout);
+                    }
+                }
+                None => {}
+            }
+            state.variables.rename(id, new_name);
+        }
+        VariableEvent::SetValue(id, value) => state.variables.set_value(id, value),
+        VariableEvent::Move { kind, from, to } => {
+            let len = match kind {
+                VariableKind::Input => table.inputs.len(),
+                VariableKind::Output => table.outputs.len(),
+            };
+            let mut order: Vec<usize> = (0..len).collect();
+            shift_vec(from, to, &mut order);
+
+            match kind {
+                VariableKind::Input => table.permute_inputs(&order),
+                VariableKind::Output => table.permute_outputs(&order),
+            }
+            state.variables.shift(kind, from, to);
+        }
+    }
+    Ok(())
+}
+
+/// Picks a path to save to, asking when `path` is empty and the project
+/// has never been saved
+fn save_path(state: &AppState, path: Option<PathBuf>) -> Option<PathBuf> {
+    path.or_else(|| state.project_path.clone()).or_else(|| {
+        rfd::FileDialog::new()
+            .add_filter("Karny project", &[project::EXTENSION])
+            .set_file_name(format!("untitled.{}", project::EXTENSION))
+            .save_file()
+    })
+}
+
+fn handle_menu_event(state: &mut AppState, ev: MenuEvent) {
+    match ev {
+        MenuEvent::NewProject => {
+            state.history.clear();
+            state.variables = Default::default();
+            state.table = Default::default();
+            state.map = Default::default();
+            state.expression = Default::default();
+            state.tableau = Default::default();
+            state.selection = Default::default();
+            state.project_path = None;
+        }
+        MenuEvent::SaveProject(path) => {
+            let Some(path) = save_path(state, path) else {
+                return;
+            };
+
+            match ProjectFile::capture(state).save(&path) {
+                Ok(()) => state.project_path = Some(path),
+                Err(err) => state.modals.error = Some(format!("Could not save project: {err}")),
+            }
+        }
+        MenuEvent::OpenProject(path) => {
+            match ProjectFile::load(&path).and_then(|file| file.restore(state)) {
+                Ok(()) => {
+                    state.history.clear();
+                    state.project_path = Some(path);
+                }
+                Err(err) => state.modals.error = Some(format!("Could not open project: {err}")),
+            }
+        }
+        MenuEvent::OpenSettings => {}
     }
 }
 
 pub enum Event {
     Variable(VariableEvent),
+    Table(TableEvent),
+    Menu(MenuEvent),
+
+    Undo,
+    Redo,
 }
 
 #[derive(Default)]
@@ -23,23 +237,52 @@ impl EventQueue {
     pub fn push(&mut self, ev: Event) {
         self.events.push(ev);
     }
-    
+
     pub fn push_variable(&mut self, ev: VariableEvent) {
         self.push(Event::Variable(ev));
     }
-    
+
+    pub fn push_table(&mut self, ev: TableEvent) {
+        self.push(Event::Table(ev));
+    }
+
+    pub fn push_menu(&mut self, ev: MenuEvent) {
+        self.push(Event::Menu(ev));
+    }
+
     pub fn take_all(&mut self) -> Vec<Event> {
         std::mem::take(&mut self.events)
     }
 }
 
+/// Applies events in order, recording variable and table edits in
+/// [`AppState::history`] so they can be undone
 pub fn dispatch_all<I>(state: &mut AppState, events: I)
 where
     I: IntoIterator<Item = Event>,
 {
     for ev in events {
         match ev {
-            Event::Variable(ev) => handle_variable_event(state, ev),
+            Event::Variable(ev) => {
+                let (label, merge) = describe_variable_event(state, &ev);
+                if let Err(err) = history::record(state, label, merge, |state| {
+                    handle_variable_event(state, ev)
+                }) {
+                    state.modals.error = Some(err);
+                }
+            }
+            Event::Table(ev) => {
+                let label = describe_table_event(state, &ev);
+                if let Err(err) =
+                    history::record(state, label, None, |state| handle_table_event(state, ev))
+                {
+                    state.modals.error = Some(err);
+                }
+            }
+            Event::Menu(ev) => handle_menu_event(state, ev),
+
+            Event::Undo => history::undo(state),
+            Event::Redo => history::redo(state),
         }
     }
-}
\ No newline at end of file
+}
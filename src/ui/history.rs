@@ -0,0 +1,451 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::{
+    app::AppState,
+    logic::{truth_table::TruthTable, variable::BitValue},
+    ui::{
+        components::map_view::AxisLayout,
+        selection::Selection,
+        variable::{VariableId, VariableStore},
+    },
+};
+
+/// Oldest entries are dropped past this many undo steps
+pub const LIMIT: usize = 100;
+
+/// Oldest entries are also dropped once the stored tables hold more rows than
+/// this, eight copies of a 16-input table
+const ROW_BUDGET: usize = 8 << 16;
+
+/// The table as it was before an edit
+#[derive(Clone, Debug)]
+enum TableState {
+    /// Kept whole when the edit changed the inputs or outputs
+    Whole(TruthTable),
+    /// Only the `(row, output, value)` cells that differ, when the edit left
+    /// the columns alone
+    Cells {
+        cells: Vec<(usize, usize, BitValue)>,
+        conflicts: BTreeSet<(usize, usize)>,
+    },
+}
+
+/// The undoable part of the app state
+#[derive(Clone, Debug)]
+struct Snapshot {
+    variables: VariableStore,
+    table: TableState,
+    layouts: BTreeMap<String, AxisLayout>,
+    selection: Selection,
+}
+
+impl Snapshot {
+    fn take(state: &AppState) -> Self {
+        Self::with_table(state, TableState::Whole(state.table.clone()))
+    }
+
+    /// Like [`Snapshot::take`], but only reads the cells `like` holds
+    fn take_like(state: &AppState, like: &Snapshot) -> Self {
+        let table = match &like.table {
+            TableState::Whole(_) => TableState::Whole(state.table.clone()),
+            TableState::Cells { cells, .. } => TableState::Cells {
+                cells: cells
+                    .iter()
+                    .map(|&(row, output, _)| (row, output, state.table.rows[row].outputs[output]))
+                    .collect(),
+                conflicts: state.table.conflicts.clone(),
+            },
+        };
+        Self::with_table(state, table)
+    }
+
+    fn with_table(state: &AppState, table: TableState) -> Self {
+        Self {
+            variables: state.variables.clone(),
+            table,
+            layouts: state.map.layouts.clone(),
+            selection: state.selection,
+        }
+    }
+
+    /// Keeps only the cells `state` changed since this snapshot was taken,
+    /// if its inputs and outputs are still the same
+    fn shrink(&mut self, state: &AppState) {
+        let TableState::Whole(before) = &self.table else {
+            return;
+        };
+        let after = &state.table;
+        if before.inputs != after.inputs || before.outputs != after.outputs {
+            return;
+        }
+
+        let mut cells = Vec::new();
+        for (row, (old, new)) in before.rows.iter().zip(&after.rows).enumerate() {
+            for (output, (&a, &b)) in old.outputs.iter().zip(&new.outputs).enumerate() {
+                if a != b {
+                    cells.push((row, output, a));
+                }
+            }
+        }
+        self.table = TableState::Cells {
+            cells,
+            conflicts: before.conflicts.clone(),
+        };
+    }
+
+    /// Rows of table data held, counted against [`ROW_BUDGET`]
+    fn rows(&self) -> usize {
+        match &self.table {
+            TableState::Whole(table) => table.rows.len(),
+            TableState::Cells { cells, .. } => cells.len(),
+        }
+    }
+
+    fn restore(self, state: &mut AppState) {
+        match self.table {
+            TableState::Whole(table) => state.table = table,
+            TableState::Cells { cells, conflicts } => {
+                for (row, output, value) in cells {
+                    state.table.rows[row].outputs[output] = value;
+                }
+                state.table.conflicts = conflicts;
+            }
+        }
+        state.variables = self.variables;
+        state.map.layouts = self.layouts;
+        state.selection = self.selection;
+    }
+}
+
+/// Lets consecutive edits of the same kind collapse into one entry
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Merge {
+    /// Keystrokes while renaming one variable
+    Rename(VariableId),
+}
+
+#[derive(Clone, Debug)]
+struct Entry {
+    label: String,
+    merge: Option<Merge>,
+    /// State from before the edit for undo entries, after it for redo entries
+    snapshot: Snapshot,
+}
+
+/// Undo and redo stacks of state snapshots, each labelled with the event
+/// that changed it
+#[derive(Debug, Default)]
+pub struct History {
+    undo: Vec<Entry>,
+    redo: Vec<Entry>,
+}
+
+impl History {
+    /// Labels of undoable edits, oldest first
+    pub fn undo_labels(&self) -> impl DoubleEndedIterator<Item = &str> {
+        self.undo.iter().map(|e| e.label.as_str())
+    }
+
+    /// Labels of redoable edits, next redo first
+    pub fn redo_labels(&self) -> impl DoubleEndedIterator<Item = &str> {
+        self.redo.iter().rev().map(|e| e.label.as_str())
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+    }
+
+    /// Drops the oldest undo steps past [`LIMIT`] or [`ROW_BUDGET`], always
+    /// keeping the latest one
+    fn trim(&mut self) {
+        let mut rows: usize = self.undo.iter().map(|e| e.snapshot.rows()).sum();
+        let mut drop = 0;
+        while drop + 1 < self.undo.len() && (self.undo.len() - drop > LIMIT || rows > ROW_BUDGET) {
+            rows -= self.undo[drop].snapshot.rows();
+            drop += 1;
+        }
+        self.undo.drain(..drop);
+    }
+}
+
+/// Applies `edit`, saving the state from before it as an undo step
+///
+/// Nothing is recorded when the edit fails, so the history never holds
+/// steps that changed nothing. Edits that leave the inputs and outputs alone
+/// only store the cells they changed.
+///
+/// ### Parameters
+/// - `label`: Description shown in the history panel
+/// - `merge`: When equal to the latest entry's key, the edit joins that
+///   entry instead of creating a new one
+/// - `edit`: Applies the change to the state
+pub fn record<E>(
+    state: &mut AppState,
+    label: String,
+    merge: Option<Merge>,
+    edit: impl FnOnce(&mut AppState) -> Result<(), E>,
+) -> Result<(), E> {
+    let merged = merge.is_some() && state.history.undo.last().is_some_and(|e| e.merge == merge);
+    let snapshot = (!merged).then(|| Snapshot::take(state));
+    edit(state)?;
+
+    state.history.redo.clear();
+    if let Some(mut snapshot) = snapshot {
+        snapshot.shrink(state);
+        let history = &mut state.history;
+        history.undo.push(Entry {
+            label,
+            merge,
+            snapshot,
+        });
+        history.trim();
+    }
+    Ok(())
+}
+
+pub fn undo(state: &mut AppState) {
+    if let Some(entry) = state.history.undo.pop() {
+        let current = Snapshot::take_like(state, &entry.snapshot);
+        state.history.redo.push(Entry {
+            label: entry.label,
+            merge: None,
+            snapshot: current,
+        });
+        entry.snapshot.restore(state);
+    }
+}
+
+pub fn redo(state: &mut AppState) {
+    if let Some(entry) = state.history.redo.pop() {
+        let current = Snapshot::take_like(state, &entry.snapshot);
+        state.history.undo.push(Entry {
+            label: entry.label,
+            merge: None,
+            snapshot: current,
+        });
+        entry.snapshot.restore(state);
+    }
+}
+
+#[cfg(test)]
+fn outputs(state: &AppState) -> Vec<crate::logic::variable::BitValue> {
+    state.table.rows.iter().map(|row| row.outputs[0]).collect()
+}
+
+#[test]
+fn undoes_and_redoes_edits() {
+    use crate::{
+        logic::variable::{BitValue::*, VariableKind},
+        ui::events::{Event, TableEvent, VariableEvent, dispatch_all},
+    };
+
+    let add = |name: &str, kind| {
+        Event::Variable(VariableEvent::Add {
+            name: name.to_string(),
+            kind,
+            value: DontCare,
+        })
+    };
+
+    let mut state = AppState::default();
+    dispatch_all(
+        &mut state,
+        [
+            add("A", VariableKind::Input),
+            add("F", VariableKind::Output),
+            Event::Table(TableEvent::Toggle { row: 1, output: 0 }),
+            Event::Table(TableEvent::Fill {
+                output: 0,
+                value: One,
+            }),
+        ],
+    );
+    assert_eq!(outputs(&state), vec![One, One]);
+    assert_eq!(
+        state.history.undo_labels().collect::<Vec<_>>(),
+        vec![
+            "Add input A",
+            "Add output F",
+            "Edit F row 1",
+            "Fill F with 1"
+        ]
+    );
+
+    // A misclicked fill comes back in one step
+    dispatch_all(&mut state, [Event::Undo]);
+    assert_eq!(outputs(&state), vec![DontCare, Zero]);
+
+    dispatch_all(&mut state, [Event::Undo, Event::Undo, Event::Undo]);
+    assert!(state.variables.inputs.is_empty());
+    assert!(state.table.inputs.is_empty());
+    assert!(!state.history.can_undo());
+
+    dispatch_all(&mut state, [Event::Redo, Event::Redo, Event::Redo]);
+    assert_eq!(outputs(&state), vec![DontCare, Zero]);
+    assert_eq!(
+        state.history.redo_labels().collect::<Vec<_>>(),
+        vec!["Fill F with 1"]
+    );
+
+    // A new edit drops the redo stack
+    dispatch_all(
+        &mut state,
+        [Event::Table(TableEvent::Toggle { row: 0, output: 0 })],
+    );
+    assert!(!state.history.can_redo());
+}
+
+#[test]
+fn merges_keystrokes_of_one_rename() {
+    use crate::{
+        logic::variable::{BitValue, VariableKind},
+        ui::events::{Event, VariableEvent, dispatch_all},
+    };
+
+    let mut state = AppState::default();
+    dispatch_all(
+        &mut state,
+        [Event::Variable(VariableEvent::Add {
+            name: "A".to_string(),
+            kind: VariableKind::Input,
+            value: BitValue::DontCare,
+        })],
+    );
+
+    let id = state.variables.inputs[0].id;
+    let rename = |name: &str| Event::Variable(VariableEvent::Rename(id, name.to_string()));
+    dispatch_all(&mut state, [rename("Ab"), rename("Abc")]);
+    assert_eq!(state.table.inputs, vec!["Abc"]);
+    assert_eq!(state.history.undo_labels().count(), 2);
+
+    dispatch_all(&mut state, [Event::Undo]);
+    assert_eq!(state.table.inputs, vec!["A"]);
+    assert_eq!(state.variables.inputs[0].name, "A");
+}
+
+#[test]
+fn rejects_blank_and_taken_names() {
+    use crate::{
+        logic::variable::{BitValue, VariableKind},
+        ui::events::{Event, VariableEvent, dispatch_all},
+    };
+
+    let add = |name: &str| {
+        Event::Variable(VariableEvent::Add {
+            name: name.to_string(),
+            kind: VariableKind::Output,
+            value: BitValue::DontCare,
+        })
+    };
+    let mut state = AppState::default();
+    dispatch_all(&mut state, [add("F"), add("G")]);
+
+    let id = state.variables.outputs[1].id;
+    let rename = |name: &str| Event::Variable(VariableEvent::Rename(id, name.to_string()));
+    for name in ["", "F"] {
+        dispatch_all(&mut state, [rename(name)]);
+        assert_eq!(state.table.outputs, vec!["F", "G"]);
+        assert_eq!(state.variables.outputs[1].name, "G");
+        assert!(state.modals.error.take().is_some());
+    }
+    assert_eq!(state.history.undo_labels().count(), 2);
+}
+
+#[test]
+fn skips_edits_that_fail() {
+    use crate::{
+        logic::{
+            gray::MAX_VARIABLES,
+            variable::{BitValue, VariableKind},
+        },
+        ui::events::{Event, VariableEvent, dispatch_all},
+    };
+
+    let add = |i: usize| {
+        Event::Variable(VariableEvent::Add {
+            name: format!("X{i}"),
+            kind: VariableKind::Input,
+            value: BitValue::DontCare,
+        })
+    };
+
+    let mut state = AppState::default();
+    dispatch_all(&mut state, (0..MAX_VARIABLES).map(add));
+    assert_eq!(state.history.undo_labels().count(), MAX_VARIABLES);
+    assert!(state.modals.error.is_none());
+
+    // The table is full, the extra input is reported and leaves no undo step
+    dispatch_all(&mut state, [add(MAX_VARIABLES)]);
+    assert_eq!(state.history.undo_labels().count(), MAX_VARIABLES);
+    assert_eq!(state.variables.inputs.len(), MAX_VARIABLES);
+    assert!(state.modals.error.is_some());
+}
+
+#[test]
+fn keeps_cell_edits_small() {
+    use crate::{
+        logic::{
+            gray::MAX_VARIABLES,
+            variable::{BitValue::*, VariableKind},
+        },
+        ui::events::{Event, TableEvent, VariableEvent, dispatch_all},
+    };
+
+    let add = |name: String, kind| {
+        Event::Variable(VariableEvent::Add {
+            name,
+            kind,
+            value: DontCare,
+        })
+    };
+
+    let mut state = AppState::default();
+    dispatch_all(
+        &mut state,
+        (0..MAX_VARIABLES).map(|i| add(format!("X{i}"), VariableKind::Input)),
+    );
+    dispatch_all(&mut state, [add("F".to_string(), VariableKind::Output)]);
+
+    // A toggle only keeps the one cell it changed, and the selection it had
+    state.selection = Selection::Cell {
+        row: 5,
+        output: Some(0),
+    };
+    dispatch_all(
+        &mut state,
+        [Event::Table(TableEvent::Toggle { row: 5, output: 0 })],
+    );
+    assert_eq!(state.history.undo.last().unwrap().snapshot.rows(), 1);
+
+    state.selection = Selection::None;
+    dispatch_all(&mut state, [Event::Undo]);
+    assert_eq!(state.table.rows[5].outputs[0], DontCare);
+    assert_eq!(
+        state.selection,
+        Selection::Cell {
+            row: 5,
+            output: Some(0)
+        }
+    );
+    dispatch_all(&mut state, [Event::Redo]);
+    assert_eq!(state.table.rows[5].outputs[0], Zero);
+
+    // Every new output copies the whole table, old steps make room
+    dispatch_all(
+        &mut state,
+        (0..8).map(|i| add(format!("G{i}"), VariableKind::Output)),
+    );
+    let rows: usize = state.history.undo.iter().map(|e| e.snapshot.rows()).sum();
+    assert!(rows <= ROW_BUDGET);
+    assert!(state.history.undo_labels().count() < MAX_VARIABLES + 10);
+    assert_eq!(state.history.undo_labels().last(), Some("Add output G7"));
+}
@@ -1,5 +1,8 @@
 pub mod components;
 pub mod events;
+pub mod history;
 pub mod modals;
+pub mod selection;
+pub mod solutions;
 
 pub mod variable;
@@ -0,0 +1,20 @@
+pub fn update(ctx: &egui::Context, error: &mut Option<String>) {
+    let Some(message) = error.clone() else {
+        return;
+    };
+
+    egui::Modal::new(egui::Id::new("error")).show(ctx, |ui| {
+        ui.set_max_width(320.0);
+
+        ui.heading("Error");
+        ui.separator();
+        ui.label(message);
+        ui.separator();
+
+        ui.centered_and_justified(|ui| {
+            if ui.button("OK").clicked() {
+                *error = None;
+            }
+        });
+    });
+}
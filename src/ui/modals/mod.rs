@@ -1,21 +1,27 @@
 pub mod add_variable;
+pub mod error;
 
-use crate::{
-    app::AppState,
-    ui::events::EventQueue
-};
+use crate::app::AppState;
 
 pub enum ModalKind {
-    AddVariable
+    AddVariable,
 }
 
 #[derive(Debug, Default)]
 pub struct ModalState {
     pub add_variable: add_variable::AddVariableState,
+    /// Message shown in the error modal until dismissed
+    pub error: Option<String>,
 }
 
 pub fn update(ctx: &egui::Context, app_state: &mut AppState) {
     if app_state.modals.add_variable.show {
-        add_variable::update(ctx, &mut app_state.modals.add_variable, &mut app_state.events);
+        add_variable::update(
+            ctx,
+            &mut app_state.modals.add_variable,
+            &mut app_state.events,
+        );
     }
+
+    error::update(ctx, &mut app_state.modals.error);
 }
@@ -0,0 +1,26 @@
+use crate::{
+    logic::solver::{Form, Implicant},
+    ui::variable::VariableId,
+};
+
+/// What the properties panel describes
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Selection {
+    #[default]
+    None,
+    Variable(VariableId),
+    /// A truth table row, narrowed to one output when picked from an output
+    /// cell or the K-map
+    Cell {
+        row: usize,
+        output: Option<usize>,
+    },
+}
+
+/// The implicant under the pointer on the map, highlighted by the other views
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct HoveredTerm {
+    pub output: usize,
+    pub form: Form,
+    pub implicant: Implicant,
+}
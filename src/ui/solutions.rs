@@ -0,0 +1,224 @@
+use std::{
+    collections::HashMap,
+    sync::mpsc::{self, Receiver, TryRecvError},
+    thread,
+};
+
+use crate::logic::{
+    solver::{self, Form, Implicant, Solution, hazard::Hazard},
+    truth_table::TruthTable,
+    variable::BitValue,
+};
+
+/// Static hazards of a cached solution and the cover without them
+#[derive(Clone, Debug)]
+pub struct HazardCheck {
+    pub hazards: Vec<Hazard>,
+    pub free_cover: Vec<Implicant>,
+}
+
+/// The latest solution of one output and form, with the column it was solved
+/// from
+#[derive(Debug)]
+struct Solved {
+    column: Vec<BitValue>,
+    solution: Solution,
+    /// Found the first time a view shows hazards
+    hazards: Option<HazardCheck>,
+}
+
+/// A column being solved on a worker thread
+#[derive(Debug)]
+struct Job {
+    column: Vec<BitValue>,
+    result: Receiver<Solution>,
+}
+
+#[derive(Debug, Default)]
+struct Entry {
+    /// `None` until the first solve finishes
+    solved: Option<Solved>,
+    /// At most one job runs per entry, edits made meanwhile are picked up
+    /// once it finishes
+    job: Option<Job>,
+}
+
+impl Entry {
+    /// Whether `column` is what the shown solution was solved from
+    fn is_current(&self, column: impl Iterator<Item = BitValue>) -> bool {
+        self.solved
+            .as_ref()
+            .is_some_and(|s| s.column.iter().copied().eq(column))
+    }
+}
+
+/// Minimal solutions shared by the views, solved lazily per output and form
+///
+/// Only what a view asks for is solved, and an entry is only recomputed once
+/// its own output column changes, so editing one output leaves the others
+/// alone. Input names are not part of the key since they don't affect the
+/// cover.
+///
+/// Solving runs on a worker thread, since large functions take seconds. Until
+/// it finishes the previous solution stays visible and [`Self::is_solving`]
+/// tells the views to say so.
+#[derive(Debug, Default)]
+pub struct SolutionCache {
+    entries: HashMap<(usize, Form), Entry>,
+}
+
+impl SolutionCache {
+    /// The latest solution of one output in one form, `None` if the output
+    /// doesn't exist or hasn't been solved yet
+    pub fn solution(&mut self, table: &TruthTable, output: usize, form: Form) -> Option<&Solution> {
+        self.refresh(table, output, form);
+        self.solved(output, form).map(|s| &s.solution)
+    }
+
+    /// The SOP and POS solutions of one output, once both exist
+    pub fn both(&mut self, table: &TruthTable, output: usize) -> Option<[&Solution; 2]> {
+        self.refresh(table, output, Form::Sop);
+        self.refresh(table, output, Form::Pos);
+        let sop = &self.solved(output, Form::Sop)?.solution;
+        let pos = &self.solved(output, Form::Pos)?.solution;
+        Some([sop, pos])
+    }
+
+    /// The solution of one output in one form and its static hazards
+    pub fn hazards(
+        &mut self,
+        table: &TruthTable,
+        output: usize,
+        form: Form,
+    ) -> Option<(&Solution, &HazardCheck)> {
+        self.refresh(table, output, form);
+        let solved = self.entries.get_mut(&(output, form))?.solved.as_mut()?;
+        let check = solved.hazards.get_or_insert_with(|| {
+            let hazards = solved.solution.hazards();
+            HazardCheck {
+                free_cover: solved.solution.hazard_free_cover(&hazards),
+                hazards,
+            }
+        });
+        Some((&solved.solution, check))
+    }
+
+    /// Whether either form of the output is being solved, so what the views
+    /// show may be out of date
+    pub fn is_solving(&mut self, table: &TruthTable, output: usize) -> bool {
+        [Form::Sop, Form::Pos].into_iter().any(|form| {
+            self.refresh(table, output, form);
+            self.entries
+                .get(&(output, form))
+                .is_some_and(|e| e.job.is_some())
+        })
+    }
+
+    /// Whether any worker is still running, the app keeps repainting until
+    /// their results are in
+    pub fn is_busy(&self) -> bool {
+        self.entries.values().any(|e| e.job.is_some())
+    }
+
+    fn solved(&self, output: usize, form: Form) -> Option<&Solved> {
+        self.entries.get(&(output, form))?.solved.as_ref()
+    }
+
+    /// Collects a finished job and starts a new one if the column changed
+    /// since the last solve
+    fn refresh(&mut self, table: &TruthTable, output: usize, form: Form) {
+        if output >= table.outputs.len() {
+            self.entries.remove(&(output, form));
+            return;
+        }
+
+        let entry = self.entries.entry((output, form)).or_default();
+        if let Some(job) = &entry.job {
+            match job.result.try_recv() {
+                Ok(solution) => {
+                    let job = entry.job.take().expect("the job was just polled");
+                    entry.solved = Some(Solved {
+                        column: job.column,
+                        solution,
+                        hazards: None,
+                    });
+                }
+                Err(TryRecvError::Empty) => return,
+                // The worker panicked, try again below
+                Err(TryRecvError::Disconnected) => entry.job = None,
+            }
+        }
+
+        let column = table.rows.iter().map(|row| row.outputs[output]);
+        if entry.is_current(column.clone()) {
+            return;
+        }
+
+        let (sender, result) = mpsc::channel();
+        let table = table.clone();
+        thread::spawn(move || {
+            // The cache may have been dropped or moved on, nobody to tell then
+            let _ = sender.send(solver::minimize(&table, output, form));
+        });
+        entry.job = Some(Job {
+            column: column.collect(),
+            result,
+        });
+    }
+
+    /// Refreshes until the worker is done, so tests see the latest solution
+    #[cfg(test)]
+    fn settle(&mut self, table: &TruthTable, output: usize, form: Form) -> Option<&Solution> {
+        self.refresh(table, output, form);
+        while self
+            .entries
+            .get(&(output, form))
+            .is_some_and(|e| e.job.is_some())
+        {
+            thread::sleep(std::time::Duration::from_millis(1));
+            self.refresh(table, output, form);
+        }
+        self.solution(table, output, form)
+    }
+}
+
+#[test]
+fn recomputes_after_edits() {
+    let mut table = TruthTable::new(&["A", "B"], &["F", "G"]);
+    table.set(3, 0, BitValue::One);
+
+    let mut cache = SolutionCache::default();
+    let first = cache.settle(&table, 0, Form::Sop).unwrap().minterms.clone();
+    assert_eq!(first, vec![3]);
+    assert!(cache.solution(&table, 2, Form::Sop).is_none());
+
+    // The previous solution stays until the new one is in
+    table.set(1, 0, BitValue::One);
+    assert_eq!(
+        cache.solution(&table, 0, Form::Sop).unwrap().minterms,
+        vec![3]
+    );
+    assert!(cache.entries[&(0, Form::Sop)].job.is_some());
+    assert_eq!(
+        cache.settle(&table, 0, Form::Sop).unwrap().minterms,
+        vec![1, 3]
+    );
+    assert!(!cache.is_busy());
+
+    // Untouched rows are don't cares, so the POS side only sees the zero
+    table.set(0, 0, BitValue::Zero);
+    cache.settle(&table, 0, Form::Sop);
+    cache.settle(&table, 0, Form::Pos);
+    assert_eq!(cache.both(&table, 0).unwrap()[1].minterms, vec![0]);
+
+    // Only what was asked for is solved
+    assert!(!cache.entries.contains_key(&(1, Form::Sop)));
+
+    // Removing F shifts G into its place
+    table.set(2, 1, BitValue::One);
+    table.remove_output(0);
+    assert_eq!(
+        cache.settle(&table, 0, Form::Sop).unwrap().minterms,
+        vec![2]
+    );
+}
@@ -1,10 +1,11 @@
 use crate::logic::variable::*;
+use egui_dnd::utils::shift_vec;
 use std::hash::{Hash, Hasher};
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub struct VariableId(u64);
 
-#[derive(Debug, Default)]
+#[derive(Clone, Debug, Default)]
 pub struct VariableStore {
     next_variable_id: u64,
 
@@ -25,7 +26,7 @@ impl VariableStore {
 
         vec.push(Variable {
             id,
-            name: name,
+            name,
             kind,
             value,
         });
@@ -45,6 +46,28 @@ impl VariableStore {
         }
     }
 
+    pub fn set_value(&mut self, id: VariableId, value: BitValue) {
+        if let Some(var) = self.get_variable_by_id_mut(id) {
+            var.value = value;
+        }
+    }
+
+    /// Moves the variable at `from` so it lands at `to`, following
+    /// [`egui_dnd::DragUpdate`] semantics
+    pub fn shift(&mut self, kind: VariableKind, from: usize, to: usize) {
+        shift_vec(from, to, self.get_corresponding_vec_mut(kind));
+    }
+
+    /// The kind of a variable and its index within that kind
+    pub fn position(&self, id: VariableId) -> Option<(VariableKind, usize)> {
+        let var = self.get_variable_by_id(id)?;
+        let index = self
+            .get_corresponding_vec(var.kind)
+            .iter()
+            .position(|v| v.id == id)?;
+        Some((var.kind, index))
+    }
+
     pub fn get_variable_by_id(&self, id: VariableId) -> Option<&Variable> {
         self.inputs
             .iter()
@@ -97,4 +120,11 @@ pub enum VariableEvent {
 
     Remove(VariableId),
     Rename(VariableId, String),
-}
\ No newline at end of file
+    /// Changes the initial value of a variable
+    SetValue(VariableId, BitValue),
+    Move {
+        kind: VariableKind,
+        from: usize,
+        to: usize,
+    },
+}